@@ -1,4 +1,7 @@
-use std::path::Path;
+use std::{
+  collections::HashMap,
+  path::Path,
+};
 
 use anyhow::Result;
 
@@ -9,12 +12,12 @@ use log4rs::encode::pattern::PatternEncoder;
 
 use winit::{
   application::ApplicationHandler,
-  event::{WindowEvent, Ime},
+  event::{WindowEvent, Ime, Touch, TouchPhase},
   event_loop::{ActiveEventLoop, ControlFlow, EventLoop},
-  window::{WindowButtons, Window, WindowId},
+  window::{CursorIcon, Window, WindowId},
 };
 
-use crate::HalaImGui;
+use crate::{HalaImGui, HalaImGuiImeData, HalaImGuiMouseSource};
 
 /// The application context trait.
 pub trait HalaApplicationContextTrait {
@@ -68,6 +71,15 @@ pub trait HalaApplicationContextTrait {
   /// return: The result.
   fn render(&mut self) -> Result<()>;
 
+  /// Handle the window resize event.
+  /// It is not called while the window is minimized.
+  /// param width: The new width of the window.
+  /// param height: The new height of the window.
+  /// return: The result.
+  fn on_resize(&mut self, _width: u32, _height: u32) -> Result<()> {
+    Ok(())
+  }
+
   /// Handle the keyboard event.
   /// param key: The key.
   /// param is_pressed: The key is pressed or not.
@@ -96,6 +108,12 @@ pub trait HalaApplicationContextTrait {
   fn on_mouse_wheel_event(&mut self, _h: f32, _v: f32) -> Result<()> {
     Ok(())
   }
+  /// Handle the pinch gesture event, of the touch screen or the touchpad.
+  /// param delta: The change of the magnification, positive to zoom in.
+  /// return: The result.
+  fn on_pinch_event(&mut self, _delta: f32) -> Result<()> {
+    Ok(())
+  }
 
   /// Initialize the log system.
   fn init_log(&self) -> Result<()> where Self: Sized {
@@ -144,11 +162,47 @@ pub trait HalaApplicationContextTrait {
 
 }
 
+/// The state of the touches on the window.
+#[derive(Default)]
+struct HalaTouchState {
+  /// The logical positions of the touches.
+  touches: HashMap<u64, [f32; 2]>,
+  /// The touch emulating the mouse, the first finger until a second one joins.
+  primary: Option<u64>,
+  /// The center and the distance of the two finger gesture.
+  gesture: Option<([f32; 2], f32)>,
+}
+
+/// The implementation of the touch state.
+impl HalaTouchState {
+
+  /// Get the center and the distance of the touches, when there are exactly two.
+  /// return: The center and the distance.
+  fn two_finger_span(&self) -> Option<([f32; 2], f32)> {
+    if self.touches.len() != 2 {
+      return None;
+    }
+    let mut positions = self.touches.values();
+    let (a, b) = (positions.next()?, positions.next()?);
+    let center = [(a[0] + b[0]) * 0.5, (a[1] + b[1]) * 0.5];
+    let distance = ((a[0] - b[0]).powi(2) + (a[1] - b[1]).powi(2)).sqrt();
+    Some((center, distance))
+  }
+
+}
+
 /// The application struct.
 pub struct HalaApplication {
   pub context: Box<dyn HalaApplicationContextTrait>,
   window: Option<Window>,
   last_time: std::time::Instant,
+  is_minimized: bool,
+  /// The cursor applied to the window, None if the cursor is hidden.
+  cursor_icon: Option<CursorIcon>,
+  is_ime_allowed: bool,
+  /// The IME data applied to the window.
+  ime_data: Option<HalaImGuiImeData>,
+  touch_state: HalaTouchState,
 }
 
 /// Implement the ApplicationHandler trait for the HalaApplication struct.
@@ -167,8 +221,7 @@ impl ApplicationHandler for HalaApplication {
     let win_attr = Window::default_attributes()
       .with_title(self.context.get_window_title())
       .with_inner_size(win_size)
-      .with_resizable(false)
-      .with_enabled_buttons(WindowButtons::CLOSE);
+      .with_resizable(true);
     self.window = Some(event_loop.create_window(win_attr).unwrap());
 
     log::debug!("Create window \"{}\" with size {}x{}.", self.context.get_window_title(), win_size.width, win_size.height);
@@ -177,6 +230,12 @@ impl ApplicationHandler for HalaApplication {
     // So here is safe to call before_run.
     self.context.before_run(win_size.width, win_size.height, self.window.as_ref().unwrap()).unwrap();
 
+    // Render the UI at the density of the monitor which the window is on.
+    let scale_factor = self.window.as_ref().unwrap().scale_factor();
+    if let Some(imgui) = self.context.get_imgui_mut() {
+      imgui.set_scale_factor(scale_factor);
+    }
+
     self.last_time = std::time::Instant::now();
   }
 
@@ -192,6 +251,30 @@ impl ApplicationHandler for HalaApplication {
         self.context.after_run();
         event_loop.exit()
       },
+      WindowEvent::Resized(size) if window_id == window.id() => {
+        if size.width == 0 || size.height == 0 {
+          log::debug!("Window minimized.");
+          self.is_minimized = true;
+        } else {
+          log::debug!("Window resized to {}x{}.", size.width, size.height);
+          match self.context.on_resize(size.width, size.height) {
+            Ok(_) => (),
+            Err(e) => {
+              log::error!("Failed to resize the application: {}", e);
+              event_loop.exit()
+            },
+          }
+          if self.is_minimized {
+            // Do not count the minimized time into the next frame.
+            self.is_minimized = false;
+            self.last_time = std::time::Instant::now();
+          }
+          window.request_redraw();
+        }
+      },
+      WindowEvent::RedrawRequested if window_id == window.id() && self.is_minimized => {
+        // Skip the frames while minimized, the redraw is requested again after restored.
+      },
       WindowEvent::RedrawRequested if window_id == window.id() => {
         let now = std::time::Instant::now();
         let duration = now - self.last_time;
@@ -201,7 +284,11 @@ impl ApplicationHandler for HalaApplication {
         match self.context.update(delta_time, window_size.width, window_size.height) {
           Ok(_) => {
             match self.context.render() {
-              Ok(_) => window.request_redraw(),
+              Ok(_) => {
+                Self::apply_mouse_cursor(window, self.context.as_ref(), &mut self.cursor_icon);
+                Self::apply_ime(window, self.context.as_ref(), &mut self.is_ime_allowed, &mut self.ime_data);
+                window.request_redraw();
+              },
               Err(e) => {
                 log::error!("Failed to render the application: {}", e);
                 event_loop.exit()
@@ -214,6 +301,13 @@ impl ApplicationHandler for HalaApplication {
           },
         }
       },
+      WindowEvent::ScaleFactorChanged { scale_factor, .. } if window_id == window.id() => {
+        log::debug!("Window scale factor changed to {}.", scale_factor);
+        let imgui = self.context.get_imgui_mut();
+        if let Some(imgui) = imgui {
+          imgui.set_scale_factor(scale_factor);
+        }
+      },
       WindowEvent::ModifiersChanged(mods) if window_id == window.id() => {
         let imgui = self.context.get_imgui_mut();
         if let Some(imgui) = imgui {
@@ -235,7 +329,7 @@ impl ApplicationHandler for HalaApplication {
       } => {
         let mut is_ui_processed = false;
         let imgui = self.context.get_imgui_mut();
-        if let Some(imgui) = imgui {
+        if let Some(imgui) = imgui.filter(|imgui| !imgui.is_ime_composing()) {
           let is_pressed = state == winit::event::ElementState::Pressed;
           if let Some(key) = HalaImGui::to_key(physical_key) {
             imgui.add_key_event(key, is_pressed);
@@ -261,6 +355,9 @@ impl ApplicationHandler for HalaApplication {
             }
           }
           is_ui_processed = imgui.want_capture_keyboard();
+        } else if let Some(imgui) = self.context.get_imgui() {
+          // The keys editing the composition belong to the IME.
+          is_ui_processed = imgui.is_ime_composing();
         }
         if !is_ui_processed {
           if let winit::keyboard::PhysicalKey::Code(keycode) = physical_key {
@@ -274,9 +371,22 @@ impl ApplicationHandler for HalaApplication {
           }
         }
       },
+      WindowEvent::Ime(Ime::Preedit(text, cursor)) => {
+        let imgui = self.context.get_imgui_mut();
+        if let Some(imgui) = imgui {
+          imgui.set_ime_preedit(&text, cursor);
+        }
+      },
+      WindowEvent::Ime(Ime::Disabled) => {
+        let imgui = self.context.get_imgui_mut();
+        if let Some(imgui) = imgui {
+          imgui.clear_ime_preedit();
+        }
+      },
       WindowEvent::Ime(Ime::Commit(text)) => {
         let imgui = self.context.get_imgui_mut();
         if let Some(imgui) = imgui {
+          imgui.clear_ime_preedit();
           for c in text.chars() {
             imgui.add_input_character(c as u32);
           }
@@ -295,6 +405,7 @@ impl ApplicationHandler for HalaApplication {
           let position = position.to_logical::<f32>(scale);
           x = position.x;
           y = position.y;
+          imgui.add_mouse_source_event(HalaImGuiMouseSource::Mouse);
           imgui.add_mouse_pos_event(x, y);
           is_ui_processed = imgui.want_capture_mouse();
         }
@@ -335,6 +446,7 @@ impl ApplicationHandler for HalaApplication {
         if let Some(imgui) = imgui {
           if let Some(button) = HalaImGui::to_button(button) {
             let is_pressed = state == winit::event::ElementState::Pressed;
+            imgui.add_mouse_source_event(HalaImGuiMouseSource::Mouse);
             imgui.add_mouse_button_event(button, is_pressed);
             is_ui_processed = imgui.want_capture_mouse();
           }
@@ -368,6 +480,7 @@ impl ApplicationHandler for HalaApplication {
               (pos.x as f32 / scale, pos.y as f32 / scale)
             },
           };
+          imgui.add_mouse_source_event(HalaImGuiMouseSource::Mouse);
           imgui.add_mouse_wheel_event(h, v);
           is_ui_processed = imgui.want_capture_mouse();
         }
@@ -381,6 +494,28 @@ impl ApplicationHandler for HalaApplication {
           }
         }
       },
+      WindowEvent::Touch(touch) => {
+        let scale_factor = window.scale_factor();
+        match self.handle_touch(touch, scale_factor) {
+          Ok(_) => (),
+          Err(e) => {
+            log::error!("Failed to handle touch event: {}", e);
+            event_loop.exit()
+          },
+        }
+      },
+      WindowEvent::PinchGesture {
+        delta,
+        ..
+      } => {
+        match self.dispatch_pinch(delta as f32) {
+          Ok(_) => (),
+          Err(e) => {
+            log::error!("Failed to handle pinch event: {}", e);
+            event_loop.exit()
+          },
+        }
+      },
       WindowEvent::Focused(is_focused) => {
         let imgui = self.context.get_imgui_mut();
         if let Some(imgui) = imgui {
@@ -402,7 +537,225 @@ impl HalaApplication {
       context,
       window: None,
       last_time: std::time::Instant::now(),
+      is_minimized: false,
+      cursor_icon: Some(CursorIcon::Default),
+      is_ime_allowed: false,
+      ime_data: None,
+      touch_state: HalaTouchState::default(),
+    }
+  }
+
+  /// Handle the touch event.
+  /// The primary touch emulates the left mouse button, two fingers scroll and pinch.
+  /// param touch: The touch.
+  /// param scale_factor: The scale factor of the window.
+  /// return: The result.
+  fn handle_touch(&mut self, touch: Touch, scale_factor: f64) -> Result<()> {
+    let source = HalaImGui::to_mouse_source(&touch);
+    let position = touch.location.to_logical::<f32>(scale_factor);
+    let position = [position.x, position.y];
+
+    match touch.phase {
+      TouchPhase::Started => {
+        self.touch_state.touches.insert(touch.id, position);
+        match self.touch_state.touches.len() {
+          1 => {
+            self.touch_state.primary = Some(touch.id);
+            self.emulate_mouse_move(source, position)?;
+            self.emulate_mouse_button(source, true)?;
+          },
+          2 => {
+            // The second finger starts a gesture, release the primary touch so it does not drag.
+            if self.touch_state.primary.take().is_some() {
+              self.emulate_mouse_button(source, false)?;
+            }
+            self.touch_state.gesture = self.touch_state.two_finger_span();
+            if let Some((center, _)) = self.touch_state.gesture {
+              // ImGUI scrolls the window under the mouse.
+              self.emulate_mouse_move(source, center)?;
+            }
+          },
+          _ => self.touch_state.gesture = None,
+        }
+      },
+      TouchPhase::Moved => {
+        match self.touch_state.touches.get_mut(&touch.id) {
+          Some(last_position) => *last_position = position,
+          None => return Ok(()),
+        }
+        if self.touch_state.primary == Some(touch.id) {
+          self.emulate_mouse_move(source, position)?;
+        } else if let (Some((last_center, last_distance)), Some((center, distance))) = (self.touch_state.gesture, self.touch_state.two_finger_span()) {
+          self.touch_state.gesture = Some((center, distance));
+          self.emulate_mouse_move(source, center)?;
+          self.emulate_mouse_wheel(source, [center[0] - last_center[0], center[1] - last_center[1]])?;
+          if last_distance > 0.0 && distance != last_distance {
+            self.dispatch_pinch(distance / last_distance - 1.0)?;
+          }
+        }
+      },
+      TouchPhase::Ended | TouchPhase::Cancelled => {
+        if self.touch_state.touches.remove(&touch.id).is_none() {
+          return Ok(());
+        }
+        let was_gesture = self.touch_state.gesture.is_some();
+        self.touch_state.gesture = self.touch_state.two_finger_span();
+        if self.touch_state.primary == Some(touch.id) {
+          self.touch_state.primary = None;
+          self.emulate_mouse_move(source, position)?;
+          self.emulate_mouse_button(source, false)?;
+        } else if !was_gesture || self.touch_state.gesture.is_some() {
+          return Ok(());
+        }
+        // Nothing is hovered without a finger on the screen.
+        self.emulate_mouse_leave(source);
+      },
+    }
+
+    Ok(())
+  }
+
+  /// Emulate the mouse move with a touch.
+  /// param source: The mouse source.
+  /// param position: The logical position.
+  /// return: The result.
+  fn emulate_mouse_move(&mut self, source: HalaImGuiMouseSource, position: [f32; 2]) -> Result<()> {
+    let mut is_ui_processed = false;
+    let imgui = self.context.get_imgui_mut();
+    if let Some(imgui) = imgui {
+      imgui.add_mouse_source_event(source);
+      imgui.add_mouse_pos_event(position[0], position[1]);
+      is_ui_processed = imgui.want_capture_mouse();
+    }
+    if !is_ui_processed {
+      self.context.on_mouse_cursor_event(position[0], position[1])?;
+    }
+
+    Ok(())
+  }
+
+  /// Emulate the mouse leaving the window after the last finger is lifted.
+  /// Only ImGUI is told, the application has no cursor position to receive.
+  /// param source: The mouse source.
+  fn emulate_mouse_leave(&mut self, source: HalaImGuiMouseSource) {
+    if let Some(imgui) = self.context.get_imgui_mut() {
+      imgui.add_mouse_source_event(source);
+      // ImGUI takes -f32::MAX as no mouse, +f32::MAX is a valid position far away.
+      imgui.add_mouse_pos_event(-f32::MAX, -f32::MAX);
+    }
+  }
+
+  /// Emulate the left mouse button with a touch.
+  /// param source: The mouse source.
+  /// param is_pressed: The button is pressed or not.
+  /// return: The result.
+  fn emulate_mouse_button(&mut self, source: HalaImGuiMouseSource, is_pressed: bool) -> Result<()> {
+    let mut is_ui_processed = false;
+    let imgui = self.context.get_imgui_mut();
+    if let Some(imgui) = imgui {
+      imgui.add_mouse_source_event(source);
+      imgui.add_mouse_button_event(imgui::MouseButton::Left, is_pressed);
+      is_ui_processed = imgui.want_capture_mouse();
+    }
+    if !is_ui_processed {
+      self.context.on_mouse_button_event(winit::event::MouseButton::Left, is_pressed)?;
+    }
+
+    Ok(())
+  }
+
+  /// Emulate the mouse wheel with the two finger pan.
+  /// param source: The mouse source.
+  /// param delta: The logical pixels which the fingers moved.
+  /// return: The result.
+  fn emulate_mouse_wheel(&mut self, source: HalaImGuiMouseSource, delta: [f32; 2]) -> Result<()> {
+    let mut is_ui_processed = false;
+    let mut h: f32 = 0.0;
+    let mut v: f32 = 0.0;
+    let imgui = self.context.get_imgui_mut();
+    if let Some(imgui) = imgui {
+      // The content follows the fingers, one line per font size.
+      let f_scale = imgui.get_font_size();
+      (h, v) = (delta[0] / f_scale, delta[1] / f_scale);
+      imgui.add_mouse_source_event(source);
+      imgui.add_mouse_wheel_event(h, v);
+      is_ui_processed = imgui.want_capture_mouse();
+    }
+    if !is_ui_processed {
+      self.context.on_mouse_wheel_event(h, v)?;
+    }
+
+    Ok(())
+  }
+
+  /// Forward the pinch gesture to the application, unless ImGUI uses the mouse.
+  /// param delta: The change of the magnification.
+  /// return: The result.
+  fn dispatch_pinch(&mut self, delta: f32) -> Result<()> {
+    let is_ui_processed = self.context.get_imgui().is_some_and(|imgui| imgui.want_capture_mouse());
+    if !is_ui_processed {
+      self.context.on_pinch_event(delta)?;
+    }
+
+    Ok(())
+  }
+
+  /// Enable the IME while ImGUI wants the text input, and place the IME candidate window at the text caret.
+  /// param window: The window.
+  /// param context: The application context.
+  /// param is_ime_allowed: Whether the IME is allowed on the window, updated when changed.
+  /// param ime_data: The IME data applied to the window, updated when changed.
+  fn apply_ime(
+    window: &Window,
+    context: &dyn HalaApplicationContextTrait,
+    is_ime_allowed: &mut bool,
+    ime_data: &mut Option<HalaImGuiImeData>,
+  ) {
+    let (want_text_input, requested) = match context.get_imgui() {
+      Some(imgui) => (imgui.want_text_input(), imgui.get_ime_data()),
+      None => (false, None),
+    };
+    if want_text_input != *is_ime_allowed {
+      log::debug!("IME {}.", if want_text_input { "allowed" } else { "disallowed" });
+      window.set_ime_allowed(want_text_input);
+      *is_ime_allowed = want_text_input;
+      *ime_data = None;
+    }
+    if !want_text_input || requested.is_none() || requested == *ime_data {
+      return;
+    }
+
+    let data = requested.unwrap();
+    window.set_ime_cursor_area(
+      winit::dpi::LogicalPosition::new(data.position[0], data.position[1]),
+      winit::dpi::LogicalSize::new(1.0, data.line_height),
+    );
+    *ime_data = requested;
+  }
+
+  /// Apply the mouse cursor requested by the ImGUI frame to the window.
+  /// param window: The window.
+  /// param context: The application context.
+  /// param cursor_icon: The cursor applied to the window, updated when changed.
+  fn apply_mouse_cursor(window: &Window, context: &dyn HalaApplicationContextTrait, cursor_icon: &mut Option<CursorIcon>) {
+    let requested = match context.get_imgui().and_then(|imgui| imgui.get_os_cursor_icon()) {
+      Some(requested) => requested,
+      None => return,
+    };
+    if requested == *cursor_icon {
+      return;
+    }
+
+    match requested {
+      Some(icon) => {
+        if cursor_icon.is_none() {
+          window.set_cursor_visible(true);
+        }
+        window.set_cursor(icon);
+      },
+      None => window.set_cursor_visible(false),
     }
+    *cursor_icon = requested;
   }
 
   /// Run the application.
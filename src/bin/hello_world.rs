@@ -13,6 +13,7 @@ use hala_imgui::{
 /// The hello world renderer.
 struct HelloWorldRenderer {
   graphics_command_buffers: hala_gfx::HalaCommandBufferSet,
+  num_of_command_buffers: usize,
   context: Rc<RefCell<hala_gfx::HalaContext>>,
 
   image_index: usize,
@@ -35,13 +36,14 @@ impl HelloWorldRenderer {
   pub fn new(name: &str, gpu_req: &hala_gfx::HalaGPURequirements, window: &winit::window::Window) -> Result<Self> {
     log::debug!("Create a new Renderer \"{}\".", name);
     let context = hala_gfx::HalaContext::new(name, gpu_req, window)?;
+    let num_of_images = context.swapchain.num_of_images;
 
     let graphics_command_buffers = hala_gfx::HalaCommandBufferSet::new(
       Rc::clone(&context.logical_device),
       Rc::clone(&context.pools),
       hala_gfx::HalaCommandBufferType::GRAPHICS,
       hala_gfx::HalaCommandBufferLevel::PRIMARY,
-      context.swapchain.num_of_images,
+      num_of_images,
       "main_graphics.cmdbuf",
     )?;
 
@@ -50,6 +52,7 @@ impl HelloWorldRenderer {
       Self {
         context: Rc::new(RefCell::new(context)),
         graphics_command_buffers: graphics_command_buffers,
+        num_of_command_buffers: num_of_images,
 
         image_index: 0,
       }
@@ -88,6 +91,31 @@ impl HelloWorldRenderer {
     Ok(())
   }
 
+  /// Resize the renderer.
+  /// param width: The width of the window.
+  /// param height: The height of the window.
+  /// return: The result.
+  pub fn resize(&mut self, width: u32, height: u32) -> Result<()> {
+    self.wait_idle()?;
+    self.context.borrow_mut().reset_swapchain(width, height)?;
+
+    // The swapchain may be recreated with a different number of images.
+    let context = self.context.borrow();
+    if self.num_of_command_buffers != context.swapchain.num_of_images {
+      self.graphics_command_buffers = hala_gfx::HalaCommandBufferSet::new(
+        Rc::clone(&context.logical_device),
+        Rc::clone(&context.pools),
+        hala_gfx::HalaCommandBufferType::GRAPHICS,
+        hala_gfx::HalaCommandBufferLevel::PRIMARY,
+        context.swapchain.num_of_images,
+        "main_graphics.cmdbuf",
+      )?;
+      self.num_of_command_buffers = context.swapchain.num_of_images;
+    }
+
+    Ok(())
+  }
+
   /// Rendering.
   /// return: The result.
   pub fn render(&mut self) -> Result<()> {
@@ -166,6 +194,14 @@ impl HalaApplication for HelloWorldApp {
     Ok(())
   }
 
+  fn on_resize(&mut self, width: u32, height: u32) -> Result<()> {
+    if let Some(renderer) = self.renderer.as_mut() {
+      renderer.resize(width, height)?;
+    }
+
+    Ok(())
+  }
+
   fn after_run(&mut self) {
     if let Some(renderer) = self.renderer.take() {
       renderer.wait_idle().expect("Failed to wait the renderer idle.");
@@ -0,0 +1,163 @@
+use std::{
+  cell::RefCell,
+  rc::Rc,
+};
+
+/// The minimum capacity of the buffer in bytes.
+const MIN_CAPACITY: u64 = 64 * 1024;
+
+/// The number of consecutive frames using less than a quarter of the capacity before the buffer shrinks.
+const SHRINK_FRAMES: u32 = 300;
+
+/// The growable buffer for the ImGUI vertex/index data of one frame.
+/// The buffer lives in CpuToGpu memory which stays mapped for the lifetime of the allocation,
+/// so filling it is a plain copy into the mapped memory.
+pub(crate) struct HalaImGuiBuffer {
+  buffer: Option<hala_gfx::HalaBuffer>,
+  usage: hala_gfx::HalaBufferUsageFlags,
+  debug_name: String,
+  low_usage_frames: u32,
+}
+
+/// The implementation of the growable buffer.
+impl HalaImGuiBuffer {
+
+  /// Create a new growable buffer, the memory is allocated by the first reserve.
+  /// param usage: The usage of the buffer.
+  /// param debug_name: The debug name.
+  /// return: The growable buffer.
+  pub(crate) fn new(usage: hala_gfx::HalaBufferUsageFlags, debug_name: &str) -> Self {
+    Self {
+      buffer: None,
+      usage,
+      debug_name: debug_name.to_string(),
+      low_usage_frames: 0,
+    }
+  }
+
+  /// Get the buffer.
+  /// return: The buffer, None before the first reserve.
+  pub(crate) fn buffer(&self) -> Option<&hala_gfx::HalaBuffer> {
+    self.buffer.as_ref()
+  }
+
+  /// Get the capacity of the buffer.
+  /// return: The capacity in bytes.
+  pub(crate) fn capacity(&self) -> u64 {
+    self.buffer.as_ref().map_or(0, |buffer| buffer.size)
+  }
+
+  /// Make sure the buffer can hold the size of this frame.
+  /// The buffer grows geometrically with headroom, and shrinks after a period of low usage.
+  /// The old buffer is dropped immediately, the caller must make sure the GPU no longer uses it.
+  /// param logical_device: The logical device.
+  /// param size: The size of this frame in bytes.
+  /// return: Whether the buffer is reallocated.
+  pub(crate) fn reserve(
+    &mut self,
+    logical_device: &Rc<RefCell<hala_gfx::HalaLogicalDevice>>,
+    size: u64,
+  ) -> Result<bool, hala_gfx::HalaGfxError> {
+    let capacity = self.capacity();
+    let new_capacity = match self.next_capacity(capacity, size) {
+      Some(new_capacity) => new_capacity,
+      None => return Ok(false),
+    };
+
+    self.buffer = None;
+    self.buffer = Some(hala_gfx::HalaBuffer::new(
+      Rc::clone(logical_device),
+      new_capacity,
+      self.usage,
+      hala_gfx::HalaMemoryLocation::CpuToGpu,
+      &self.debug_name,
+    )?);
+    log::debug!("ImGUI buffer \"{}\" reallocated from {} to {} bytes for {} bytes.", self.debug_name, capacity, new_capacity, size);
+
+    Ok(true)
+  }
+
+  /// Decide the capacity of the buffer for the size of this frame.
+  /// param capacity: The current capacity in bytes.
+  /// param size: The size of this frame in bytes.
+  /// return: The new capacity, None to keep the buffer.
+  fn next_capacity(&mut self, capacity: u64, size: u64) -> Option<u64> {
+    let new_capacity = if size > capacity {
+      (capacity * 2).max(size + size / 2).max(MIN_CAPACITY)
+    } else if capacity > MIN_CAPACITY && size < capacity / 4 {
+      self.low_usage_frames += 1;
+      if self.low_usage_frames < SHRINK_FRAMES {
+        return None;
+      }
+      (size * 2).max(MIN_CAPACITY)
+    } else {
+      self.low_usage_frames = 0;
+      return None;
+    };
+
+    self.low_usage_frames = 0;
+    Some(new_capacity)
+  }
+
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  fn new_buffer() -> HalaImGuiBuffer {
+    HalaImGuiBuffer::new(hala_gfx::HalaBufferUsageFlags::VERTEX_BUFFER, "test.buffer")
+  }
+
+  #[test]
+  fn first_reserve_allocates_min_capacity() {
+    let mut buffer = new_buffer();
+    assert_eq!(buffer.capacity(), 0);
+    assert_eq!(buffer.next_capacity(0, 100), Some(MIN_CAPACITY));
+    assert_eq!(buffer.next_capacity(0, 0), None);
+  }
+
+  #[test]
+  fn grows_geometrically_with_headroom() {
+    let mut buffer = new_buffer();
+    // Doubling covers a small overflow.
+    assert_eq!(buffer.next_capacity(MIN_CAPACITY, MIN_CAPACITY + 1), Some(MIN_CAPACITY * 2));
+    // A large jump gets half of the size as headroom.
+    assert_eq!(buffer.next_capacity(MIN_CAPACITY, MIN_CAPACITY * 4), Some(MIN_CAPACITY * 6));
+    // A size fitting the capacity keeps the buffer.
+    assert_eq!(buffer.next_capacity(MIN_CAPACITY * 2, MIN_CAPACITY * 2), None);
+  }
+
+  #[test]
+  fn shrinks_after_low_usage_frames() {
+    let mut buffer = new_buffer();
+    let capacity = MIN_CAPACITY * 16;
+    let size = MIN_CAPACITY;
+    for _ in 1..SHRINK_FRAMES {
+      assert_eq!(buffer.next_capacity(capacity, size), None);
+    }
+    assert_eq!(buffer.next_capacity(capacity, size), Some(MIN_CAPACITY * 2));
+    assert_eq!(buffer.low_usage_frames, 0);
+  }
+
+  #[test]
+  fn usage_spike_resets_shrink_countdown() {
+    let mut buffer = new_buffer();
+    let capacity = MIN_CAPACITY * 16;
+    for _ in 1..SHRINK_FRAMES {
+      assert_eq!(buffer.next_capacity(capacity, MIN_CAPACITY), None);
+    }
+    assert_eq!(buffer.next_capacity(capacity, capacity / 2), None);
+    assert_eq!(buffer.next_capacity(capacity, MIN_CAPACITY), None);
+    assert_eq!(buffer.low_usage_frames, 1);
+  }
+
+  #[test]
+  fn never_shrinks_below_min_capacity() {
+    let mut buffer = new_buffer();
+    for _ in 0..SHRINK_FRAMES * 2 {
+      assert_eq!(buffer.next_capacity(MIN_CAPACITY, 0), None);
+    }
+  }
+
+}
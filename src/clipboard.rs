@@ -0,0 +1,129 @@
+use std::{
+  cell::RefCell,
+  rc::Rc,
+};
+
+use anyhow::Result;
+
+/// The in-memory clipboard, for the headless runs and the tests.
+/// The text is only shared inside the process.
+#[derive(Debug, Clone, Default)]
+pub struct HalaImGuiMemoryClipboard {
+  text: Option<String>,
+}
+
+/// The implementation of the ImGUI clipboard backend for the in-memory clipboard.
+impl imgui::ClipboardBackend for HalaImGuiMemoryClipboard {
+
+  fn get(&mut self) -> Option<String> {
+    self.text.clone()
+  }
+
+  fn set(&mut self, value: &str) {
+    self.text = Some(value.to_string());
+  }
+
+}
+
+/// The clipboard of the desktop session, X11 or Wayland.
+pub struct HalaImGuiSystemClipboard {
+  clipboard: arboard::Clipboard,
+}
+
+/// The implementation of the system clipboard.
+impl HalaImGuiSystemClipboard {
+
+  /// Connect to the clipboard of the desktop session.
+  /// return: The system clipboard.
+  pub fn new() -> Result<Self> {
+    Ok(Self {
+      clipboard: arboard::Clipboard::new()?,
+    })
+  }
+
+}
+
+/// The implementation of the ImGUI clipboard backend for the system clipboard.
+impl imgui::ClipboardBackend for HalaImGuiSystemClipboard {
+
+  fn get(&mut self) -> Option<String> {
+    self.clipboard.get_text().ok()
+  }
+
+  fn set(&mut self, value: &str) {
+    if let Err(e) = self.clipboard.set_text(value) {
+      log::warn!("Failed to set the clipboard text: {}", e);
+    }
+  }
+
+}
+
+/// The clipboard shared by the ImGUI context and the application.
+/// ImGUI owns the backend it is given, so it is given this handle and the backend stays replaceable.
+#[derive(Clone)]
+pub(crate) struct HalaImGuiClipboard {
+  backend: Rc<RefCell<Box<dyn imgui::ClipboardBackend>>>,
+}
+
+/// The implementation of the shared clipboard.
+impl HalaImGuiClipboard {
+
+  /// Create the shared clipboard with a backend.
+  /// param backend: The clipboard backend.
+  /// return: The shared clipboard.
+  pub(crate) fn new<T: imgui::ClipboardBackend>(backend: T) -> Self {
+    Self {
+      backend: Rc::new(RefCell::new(Box::new(backend))),
+    }
+  }
+
+  /// Create the shared clipboard with the system clipboard, or the in-memory one if there is no desktop session.
+  /// return: The shared clipboard.
+  pub(crate) fn with_system_or_memory() -> Self {
+    match HalaImGuiSystemClipboard::new() {
+      Ok(clipboard) => Self::new(clipboard),
+      Err(e) => {
+        log::debug!("The system clipboard is not available, use the in-memory one: {}", e);
+        Self::new(HalaImGuiMemoryClipboard::default())
+      },
+    }
+  }
+
+  /// Replace the backend.
+  /// param backend: The clipboard backend.
+  pub(crate) fn set_backend<T: imgui::ClipboardBackend>(&self, backend: T) {
+    *self.backend.borrow_mut() = Box::new(backend);
+  }
+
+  /// Get the clipboard text.
+  /// return: The text, None if the clipboard is empty or inaccessible.
+  pub(crate) fn get_text(&self) -> Option<String> {
+    self.backend.borrow_mut().get()
+  }
+
+  /// Set the clipboard text.
+  /// param text: The text.
+  pub(crate) fn set_text(&self, text: &str) {
+    self.backend.borrow_mut().set(text);
+  }
+
+}
+
+/// The implementation of the ImGUI clipboard backend for the shared clipboard.
+impl imgui::ClipboardBackend for HalaImGuiClipboard {
+
+  fn get(&mut self) -> Option<String> {
+    // ImGUI only asks for the text to paste it, so the pasted characters are rendered by the text input.
+    let text = self.get_text();
+    if let Some(text) = text.as_deref() {
+      crate::imgui::request_glyphs(text);
+    }
+
+    text
+  }
+
+  fn set(&mut self, value: &str) {
+    self.set_text(value);
+  }
+
+}
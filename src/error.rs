@@ -0,0 +1,29 @@
+/// The errors of the ImGUI context.
+#[derive(thiserror::Error, Debug)]
+pub enum HalaImGuiError {
+  /// The number of frames in flight is 0.
+  #[error("The number of ImGUI frames in flight must be greater than 0.")]
+  InvalidFramesInFlight,
+  /// HalaImGui::draw is called while the number of frames in flight is explicit.
+  #[error("The ImGUI context has {0} explicit frames in flight, draw it with draw_in_frame.")]
+  ExplicitFramesInFlight(usize),
+  /// HalaImGui::set_frames_in_flight is called while the frames in flight follow the swapchain images.
+  #[error("The ImGUI frames in flight follow the swapchain images, create the context with an explicit number to set it.")]
+  SwapchainFramesInFlight,
+  /// The frame slot passed to the draw functions is out of the frames in flight.
+  #[error("The ImGUI frame slot {0} is out of the {1} frames in flight.")]
+  InvalidFrameSlot(usize, usize),
+  /// The bindless textures are requested but the device lacks a descriptor indexing feature,
+  /// or the capacity exceeds the descriptor limits of the device.
+  #[error("The ImGUI bindless textures are not supported: {0}.")]
+  UnsupportedBindlessTextures(String),
+}
+
+/// The implementation of the conversion to the graphics error, for the draw functions.
+impl From<HalaImGuiError> for hala_gfx::HalaGfxError {
+
+  fn from(err: HalaImGuiError) -> Self {
+    hala_gfx::HalaGfxError::new(&err.to_string(), Some(Box::new(err)))
+  }
+
+}
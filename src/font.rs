@@ -0,0 +1,212 @@
+use std::{
+  collections::BTreeSet,
+  path::Path,
+};
+
+use anyhow::Result;
+
+/// The glyph ranges of a font.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum HalaImGuiGlyphRanges {
+  /// Basic Latin and Latin-1 supplement.
+  Default,
+  /// Default + Half-Width + Japanese Hiragana/Katakana + full set of about 21000 CJK Unified Ideographs.
+  ChineseFull,
+  /// Default + Half-Width + Japanese Hiragana/Katakana + set of 2500 CJK Unified Ideographs for common simplified Chinese.
+  ChineseSimplifiedCommon,
+  /// Default + Hiragana, Katakana, Half-Width, Selection of 2999 Ideographs.
+  Japanese,
+  /// Default + Korean characters.
+  Korean,
+  /// Default + about 400 Cyrillic characters.
+  Cyrillic,
+  /// Default + Thai characters.
+  Thai,
+  /// Default + Vietnamese characters.
+  Vietnamese,
+  /// The custom ranges, pairs of inclusive(first, last) code points.
+  Custom(Vec<(u32, u32)>),
+  /// Default + the characters actually in use.
+  ///
+  /// IMPORTANT: ImGUI does not report the text it renders, so a plain ui.text(...) label never adds its glyphs.
+  /// Only the typed characters, the pasted text, the IME preedit text and the text wrapped with HalaImGui::track_text are seen:
+  /// wrap every label which may contain non-Latin-1 characters, e.g. ui.text(HalaImGui::track_text(ui, label)).
+  ///
+  /// The new glyphs are added to the atlas once per frame after HalaImGui::end_frame,
+  /// and show up from the next frame.
+  OnDemand,
+}
+
+/// The first and last code points of the default glyph ranges.
+const DEFAULT_GLYPH_RANGE: (u32, u32) = (0x0020, 0x00FF);
+
+/// The source of a font, several sources are merged into one font.
+pub(crate) struct HalaImGuiFontSource {
+  /// The TTF/OTF data, None for the default ProggyClean font.
+  data: Option<Vec<u8>>,
+  size_pixels: f32,
+  glyph_ranges: HalaImGuiGlyphRanges,
+  /// The zero-terminated custom glyph ranges passed to ImGUI.
+  custom_ranges: Vec<u32>,
+}
+
+/// The implementation of the font source.
+impl HalaImGuiFontSource {
+
+  /// Create the source of the default ProggyClean font.
+  /// param size_pixels: The size of the font in logical pixels.
+  /// return: The font source.
+  pub(crate) fn with_default(size_pixels: f32) -> Self {
+    Self::new(None, size_pixels, HalaImGuiGlyphRanges::Default)
+  }
+
+  /// Create the font source from a TTF/OTF file.
+  /// param path: The path of the font file.
+  /// param size_pixels: The size of the font in logical pixels.
+  /// param glyph_ranges: The glyph ranges.
+  /// return: The font source.
+  pub(crate) fn with_file<P: AsRef<Path>>(path: P, size_pixels: f32, glyph_ranges: HalaImGuiGlyphRanges) -> Result<Self> {
+    let data = std::fs::read(path)?;
+
+    Ok(Self::new(Some(data), size_pixels, glyph_ranges))
+  }
+
+  /// Create the font source from the TTF/OTF data.
+  /// param data: The TTF/OTF data.
+  /// param size_pixels: The size of the font in logical pixels.
+  /// param glyph_ranges: The glyph ranges.
+  /// return: The font source.
+  pub(crate) fn with_memory(data: &[u8], size_pixels: f32, glyph_ranges: HalaImGuiGlyphRanges) -> Self {
+    Self::new(Some(data.to_vec()), size_pixels, glyph_ranges)
+  }
+
+  /// Create the font source.
+  /// param data: The TTF/OTF data, None for the default font.
+  /// param size_pixels: The size of the font in logical pixels.
+  /// param glyph_ranges: The glyph ranges.
+  /// return: The font source.
+  fn new(data: Option<Vec<u8>>, size_pixels: f32, glyph_ranges: HalaImGuiGlyphRanges) -> Self {
+    let custom_ranges = match &glyph_ranges {
+      HalaImGuiGlyphRanges::Custom(ranges) => {
+        let mut custom_ranges = Vec::with_capacity(ranges.len() * 2 + 1);
+        for (first, last) in ranges.iter() {
+          custom_ranges.push(*first);
+          custom_ranges.push(*last);
+        }
+        custom_ranges.push(0);
+        custom_ranges
+      },
+      HalaImGuiGlyphRanges::OnDemand => vec![DEFAULT_GLYPH_RANGE.0, DEFAULT_GLYPH_RANGE.1, 0],
+      _ => Vec::new(),
+    };
+
+    Self {
+      data,
+      size_pixels,
+      glyph_ranges,
+      custom_ranges,
+    }
+  }
+
+  /// Whether the glyphs of the source are added on demand.
+  /// return: Whether the glyphs are on demand.
+  pub(crate) fn is_on_demand(&self) -> bool {
+    self.glyph_ranges == HalaImGuiGlyphRanges::OnDemand
+  }
+
+  /// Update the glyph ranges of an on demand source.
+  /// The font atlas must be cleared before calling it.
+  /// param glyphs: The code points in use outside of the default ranges.
+  pub(crate) fn set_on_demand_glyphs(&mut self, glyphs: &BTreeSet<u32>) {
+    if !self.is_on_demand() {
+      return;
+    }
+
+    self.custom_ranges.clear();
+    self.custom_ranges.push(DEFAULT_GLYPH_RANGE.0);
+    self.custom_ranges.push(DEFAULT_GLYPH_RANGE.1);
+    // Merge the consecutive code points into one range.
+    for &c in glyphs.iter() {
+      let len = self.custom_ranges.len();
+      if c == self.custom_ranges[len - 1] + 1 {
+        self.custom_ranges[len - 1] = c;
+      } else if c > self.custom_ranges[len - 1] {
+        self.custom_ranges.push(c);
+        self.custom_ranges.push(c);
+      }
+    }
+    self.custom_ranges.push(0);
+  }
+
+  /// Convert to the ImGUI font source.
+  /// param scale_factor: The scale factor from physical pixels to logical pixels.
+  /// return: The ImGUI font source.
+  pub(crate) fn to_imgui(&self, scale_factor: f32) -> imgui::FontSource<'_> {
+    let glyph_ranges = match &self.glyph_ranges {
+      HalaImGuiGlyphRanges::Default => imgui::FontGlyphRanges::default(),
+      HalaImGuiGlyphRanges::ChineseFull => imgui::FontGlyphRanges::chinese_full(),
+      HalaImGuiGlyphRanges::ChineseSimplifiedCommon => imgui::FontGlyphRanges::chinese_simplified_common(),
+      HalaImGuiGlyphRanges::Japanese => imgui::FontGlyphRanges::japanese(),
+      HalaImGuiGlyphRanges::Korean => imgui::FontGlyphRanges::korean(),
+      HalaImGuiGlyphRanges::Cyrillic => imgui::FontGlyphRanges::cyrillic(),
+      HalaImGuiGlyphRanges::Thai => imgui::FontGlyphRanges::thai(),
+      HalaImGuiGlyphRanges::Vietnamese => imgui::FontGlyphRanges::vietnamese(),
+      // SAFETY: ImGUI keeps the pointer of the ranges until the font atlas is cleared.
+      // The ranges are owned by this source, and the atlas is always cleared before the ranges change or the source is dropped.
+      HalaImGuiGlyphRanges::Custom(_) | HalaImGuiGlyphRanges::OnDemand => imgui::FontGlyphRanges::from_slice(unsafe {
+        std::slice::from_raw_parts(self.custom_ranges.as_ptr(), self.custom_ranges.len())
+      }),
+    };
+    let config = imgui::FontConfig {
+      size_pixels: self.size_pixels * scale_factor,
+      glyph_ranges,
+      ..Default::default()
+    };
+
+    match &self.data {
+      Some(data) => imgui::FontSource::TtfData {
+        data,
+        size_pixels: self.size_pixels * scale_factor,
+        config: Some(config),
+      },
+      None => imgui::FontSource::DefaultFontData {
+        config: Some(config),
+      },
+    }
+  }
+
+}
+
+/// The font in the ImGUI context.
+pub(crate) struct HalaImGuiFont {
+  /// The sources, the first one is the base font and the others are merged into it.
+  pub(crate) sources: Vec<HalaImGuiFontSource>,
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn on_demand_glyphs_merge_into_ranges() {
+    let mut source = HalaImGuiFontSource::new(None, 13.0, HalaImGuiGlyphRanges::OnDemand);
+    let glyphs = [0x4E00, 0x4E01, 0x4E02, 0x4E10, 0x0100].into_iter().collect::<BTreeSet<u32>>();
+    source.set_on_demand_glyphs(&glyphs);
+
+    assert_eq!(source.custom_ranges, vec![
+      DEFAULT_GLYPH_RANGE.0, 0x0100,
+      0x4E00, 0x4E02,
+      0x4E10, 0x4E10,
+      0,
+    ]);
+  }
+
+  #[test]
+  fn on_demand_glyphs_ignore_fixed_ranges() {
+    let mut source = HalaImGuiFontSource::new(None, 13.0, HalaImGuiGlyphRanges::Default);
+    source.set_on_demand_glyphs(&[0x4E00].into_iter().collect());
+
+    assert!(source.custom_ranges.is_empty());
+  }
+
+}
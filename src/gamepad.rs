@@ -0,0 +1,553 @@
+use std::{
+  cell::RefCell,
+  rc::Rc,
+};
+
+use anyhow::Result;
+use imgui::internal::RawCast;
+
+/// The gamepad buttons, named by the position as in the ImGUI gamepad keys.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum HalaImGuiGamepadButton {
+  Start,
+  Back,
+  /// X on the Xbox pads, Square on the PlayStation pads.
+  FaceLeft,
+  /// B on the Xbox pads, Circle on the PlayStation pads.
+  FaceRight,
+  /// Y on the Xbox pads, Triangle on the PlayStation pads.
+  FaceUp,
+  /// A on the Xbox pads, Cross on the PlayStation pads.
+  FaceDown,
+  DpadLeft,
+  DpadRight,
+  DpadUp,
+  DpadDown,
+  L1,
+  R1,
+  /// The digital left trigger, for the pads without the analog triggers.
+  L2,
+  /// The digital right trigger, for the pads without the analog triggers.
+  R2,
+  L3,
+  R3,
+}
+
+/// The implementation of the gamepad buttons.
+impl HalaImGuiGamepadButton {
+
+  /// The number of the buttons.
+  pub const COUNT: usize = 16;
+
+  /// All the buttons.
+  pub const ALL: [Self; Self::COUNT] = [
+    Self::Start, Self::Back,
+    Self::FaceLeft, Self::FaceRight, Self::FaceUp, Self::FaceDown,
+    Self::DpadLeft, Self::DpadRight, Self::DpadUp, Self::DpadDown,
+    Self::L1, Self::R1, Self::L2, Self::R2, Self::L3, Self::R3,
+  ];
+
+  /// Get the ImGUI key of the button.
+  /// return: The ImGUI key.
+  pub fn to_key(self) -> imgui::Key {
+    match self {
+      Self::Start => imgui::Key::GamepadStart,
+      Self::Back => imgui::Key::GamepadBack,
+      Self::FaceLeft => imgui::Key::GamepadFaceLeft,
+      Self::FaceRight => imgui::Key::GamepadFaceRight,
+      Self::FaceUp => imgui::Key::GamepadFaceUp,
+      Self::FaceDown => imgui::Key::GamepadFaceDown,
+      Self::DpadLeft => imgui::Key::GamepadDpadLeft,
+      Self::DpadRight => imgui::Key::GamepadDpadRight,
+      Self::DpadUp => imgui::Key::GamepadDpadUp,
+      Self::DpadDown => imgui::Key::GamepadDpadDown,
+      Self::L1 => imgui::Key::GamepadL1,
+      Self::R1 => imgui::Key::GamepadR1,
+      Self::L2 => imgui::Key::GamepadL2,
+      Self::R2 => imgui::Key::GamepadR2,
+      Self::L3 => imgui::Key::GamepadL3,
+      Self::R3 => imgui::Key::GamepadR3,
+    }
+  }
+
+}
+
+/// The gamepad axes.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum HalaImGuiGamepadAxis {
+  /// The left stick, -1.0 is left and 1.0 is right.
+  LeftStickX,
+  /// The left stick, -1.0 is up and 1.0 is down.
+  LeftStickY,
+  /// The right stick, -1.0 is left and 1.0 is right.
+  RightStickX,
+  /// The right stick, -1.0 is up and 1.0 is down.
+  RightStickY,
+  /// The left trigger, from 0.0 to 1.0.
+  LeftTrigger,
+  /// The right trigger, from 0.0 to 1.0.
+  RightTrigger,
+}
+
+/// The implementation of the gamepad axes.
+impl HalaImGuiGamepadAxis {
+
+  /// The number of the axes.
+  pub const COUNT: usize = 6;
+
+}
+
+/// The state of a gamepad.
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub struct HalaImGuiGamepadState {
+  buttons: [bool; HalaImGuiGamepadButton::COUNT],
+  axes: [f32; HalaImGuiGamepadAxis::COUNT],
+}
+
+/// The implementation of the gamepad state.
+impl HalaImGuiGamepadState {
+
+  /// Whether the button is pressed.
+  /// param button: The button.
+  /// return: Whether the button is pressed.
+  pub fn is_pressed(&self, button: HalaImGuiGamepadButton) -> bool {
+    self.buttons[button as usize]
+  }
+
+  /// Set whether the button is pressed.
+  /// param button: The button.
+  /// param is_pressed: Whether the button is pressed.
+  pub fn set_pressed(&mut self, button: HalaImGuiGamepadButton, is_pressed: bool) {
+    self.buttons[button as usize] = is_pressed;
+  }
+
+  /// Get the value of the axis.
+  /// param axis: The axis.
+  /// return: The value, from -1.0 to 1.0 for the sticks and from 0.0 to 1.0 for the triggers.
+  pub fn get_axis(&self, axis: HalaImGuiGamepadAxis) -> f32 {
+    self.axes[axis as usize]
+  }
+
+  /// Set the value of the axis.
+  /// param axis: The axis.
+  /// param value: The value, clamped to -1.0 to 1.0 for the sticks and 0.0 to 1.0 for the triggers.
+  pub fn set_axis(&mut self, axis: HalaImGuiGamepadAxis, value: f32) {
+    self.axes[axis as usize] = match axis {
+      HalaImGuiGamepadAxis::LeftTrigger | HalaImGuiGamepadAxis::RightTrigger => value.clamp(0.0, 1.0),
+      _ => value.clamp(-1.0, 1.0),
+    };
+  }
+
+}
+
+/// The gamepad input source trait.
+pub trait HalaImGuiGamepadSource {
+
+  /// Poll the current state of the gamepad, it is called once per frame.
+  /// return: The state, None if the gamepad is disconnected.
+  fn poll(&mut self) -> Result<Option<HalaImGuiGamepadState>>;
+
+}
+
+/// The fake gamepad, driven by the code, for the tests and the automation.
+/// The clones share the state, so keep a clone to drive the one given to the ImGUI context.
+#[derive(Debug, Clone, Default)]
+pub struct HalaImGuiFakeGamepad {
+  state: Rc<RefCell<Option<HalaImGuiGamepadState>>>,
+}
+
+/// The implementation of the fake gamepad.
+impl HalaImGuiFakeGamepad {
+
+  /// Create a connected fake gamepad with nothing pressed.
+  /// return: The fake gamepad.
+  pub fn new() -> Self {
+    Self {
+      state: Rc::new(RefCell::new(Some(HalaImGuiGamepadState::default()))),
+    }
+  }
+
+  /// Set the state, it also connects the gamepad.
+  /// param state: The state.
+  pub fn set_state(&self, state: HalaImGuiGamepadState) {
+    *self.state.borrow_mut() = Some(state);
+  }
+
+  /// Set whether the button is pressed.
+  /// param button: The button.
+  /// param is_pressed: Whether the button is pressed.
+  pub fn set_pressed(&self, button: HalaImGuiGamepadButton, is_pressed: bool) {
+    self.state.borrow_mut().get_or_insert_with(Default::default).set_pressed(button, is_pressed);
+  }
+
+  /// Set the value of the axis.
+  /// param axis: The axis.
+  /// param value: The value.
+  pub fn set_axis(&self, axis: HalaImGuiGamepadAxis, value: f32) {
+    self.state.borrow_mut().get_or_insert_with(Default::default).set_axis(axis, value);
+  }
+
+  /// Disconnect the gamepad.
+  pub fn disconnect(&self) {
+    *self.state.borrow_mut() = None;
+  }
+
+}
+
+/// The implementation of the gamepad input source for the fake gamepad.
+impl HalaImGuiGamepadSource for HalaImGuiFakeGamepad {
+
+  fn poll(&mut self) -> Result<Option<HalaImGuiGamepadState>> {
+    Ok(*self.state.borrow())
+  }
+
+}
+
+/// The gamepad of Linux evdev, e.g. /dev/input/event*.
+/// The state is read with the ioctls, so the events are not consumed and the polling never blocks.
+#[cfg(target_os = "linux")]
+pub struct HalaImGuiEvdevGamepad {
+  device: evdev::Device,
+}
+
+/// The implementation of the evdev gamepad.
+#[cfg(target_os = "linux")]
+impl HalaImGuiEvdevGamepad {
+
+  /// Open the gamepad.
+  /// param path: The path of the event device.
+  /// return: The evdev gamepad.
+  pub fn open<P: AsRef<std::path::Path>>(path: P) -> Result<Self> {
+    let device = evdev::Device::open(path.as_ref())?;
+    if !Self::is_gamepad(&device) {
+      return Err(anyhow::anyhow!("The device \"{}\" is not a gamepad.", path.as_ref().to_string_lossy()));
+    }
+
+    log::debug!("Gamepad \"{}\" opened.", device.name().unwrap_or("unknown"));
+    Ok(Self { device })
+  }
+
+  /// Find the first gamepad in the event devices.
+  /// return: The evdev gamepad, None if no gamepad is found.
+  pub fn find_first() -> Option<Self> {
+    let (path, device) = evdev::enumerate().find(|(_, device)| Self::is_gamepad(device))?;
+    log::debug!("Gamepad \"{}\" found at \"{}\".", device.name().unwrap_or("unknown"), path.to_string_lossy());
+    Some(Self { device })
+  }
+
+  /// Whether the device is a gamepad, judged by the south face button as the kernel gamepad specification.
+  /// param device: The device.
+  /// return: Whether the device is a gamepad.
+  fn is_gamepad(device: &evdev::Device) -> bool {
+    device.supported_keys().is_some_and(|keys| keys.contains(evdev::Key::BTN_SOUTH))
+  }
+
+  /// Normalize the value of the absolute axis.
+  /// param info: The information of the absolute axis.
+  /// param is_signed: Whether to map to -1.0 to 1.0, otherwise 0.0 to 1.0.
+  /// return: The normalized value.
+  fn normalize(info: &libc::input_absinfo, is_signed: bool) -> f32 {
+    let range = (info.maximum - info.minimum) as f32;
+    if range <= 0.0 {
+      return 0.0;
+    }
+    let value = (info.value - info.minimum) as f32 / range;
+    if is_signed {
+      value * 2.0 - 1.0
+    } else {
+      value
+    }
+  }
+
+}
+
+/// The implementation of the gamepad input source for the evdev gamepad.
+#[cfg(target_os = "linux")]
+impl HalaImGuiGamepadSource for HalaImGuiEvdevGamepad {
+
+  fn poll(&mut self) -> Result<Option<HalaImGuiGamepadState>> {
+    let (keys, abs) = match (self.device.get_key_state(), self.device.get_abs_state()) {
+      (Ok(keys), Ok(abs)) => (keys, abs),
+      // The device node is gone after the gamepad is unplugged.
+      (Err(e), _) | (_, Err(e)) if e.raw_os_error() == Some(libc::ENODEV) => return Ok(None),
+      (Err(e), _) | (_, Err(e)) => return Err(e.into()),
+    };
+
+    let mut state = HalaImGuiGamepadState::default();
+    for (button, key) in [
+      (HalaImGuiGamepadButton::Start, evdev::Key::BTN_START),
+      (HalaImGuiGamepadButton::Back, evdev::Key::BTN_SELECT),
+      (HalaImGuiGamepadButton::FaceLeft, evdev::Key::BTN_WEST),
+      (HalaImGuiGamepadButton::FaceRight, evdev::Key::BTN_EAST),
+      (HalaImGuiGamepadButton::FaceUp, evdev::Key::BTN_NORTH),
+      (HalaImGuiGamepadButton::FaceDown, evdev::Key::BTN_SOUTH),
+      (HalaImGuiGamepadButton::DpadLeft, evdev::Key::BTN_DPAD_LEFT),
+      (HalaImGuiGamepadButton::DpadRight, evdev::Key::BTN_DPAD_RIGHT),
+      (HalaImGuiGamepadButton::DpadUp, evdev::Key::BTN_DPAD_UP),
+      (HalaImGuiGamepadButton::DpadDown, evdev::Key::BTN_DPAD_DOWN),
+      (HalaImGuiGamepadButton::L1, evdev::Key::BTN_TL),
+      (HalaImGuiGamepadButton::R1, evdev::Key::BTN_TR),
+      (HalaImGuiGamepadButton::L2, evdev::Key::BTN_TL2),
+      (HalaImGuiGamepadButton::R2, evdev::Key::BTN_TR2),
+      (HalaImGuiGamepadButton::L3, evdev::Key::BTN_THUMBL),
+      (HalaImGuiGamepadButton::R3, evdev::Key::BTN_THUMBR),
+    ] {
+      state.set_pressed(button, keys.contains(key));
+    }
+
+    let supported_axes = self.device.supported_absolute_axes();
+    let has_axis = |axis: evdev::AbsoluteAxisType| supported_axes.is_some_and(|axes| axes.contains(axis));
+    for (axis, abs_axis, is_signed) in [
+      (HalaImGuiGamepadAxis::LeftStickX, evdev::AbsoluteAxisType::ABS_X, true),
+      (HalaImGuiGamepadAxis::LeftStickY, evdev::AbsoluteAxisType::ABS_Y, true),
+      (HalaImGuiGamepadAxis::RightStickX, evdev::AbsoluteAxisType::ABS_RX, true),
+      (HalaImGuiGamepadAxis::RightStickY, evdev::AbsoluteAxisType::ABS_RY, true),
+      (HalaImGuiGamepadAxis::LeftTrigger, evdev::AbsoluteAxisType::ABS_Z, false),
+      (HalaImGuiGamepadAxis::RightTrigger, evdev::AbsoluteAxisType::ABS_RZ, false),
+    ] {
+      if has_axis(abs_axis) {
+        state.set_axis(axis, Self::normalize(&abs[abs_axis.0 as usize], is_signed));
+      }
+    }
+
+    // Many pads report the dpad as a hat instead of the buttons.
+    if has_axis(evdev::AbsoluteAxisType::ABS_HAT0X) {
+      let x = abs[evdev::AbsoluteAxisType::ABS_HAT0X.0 as usize].value;
+      state.set_pressed(HalaImGuiGamepadButton::DpadLeft, state.is_pressed(HalaImGuiGamepadButton::DpadLeft) || x < 0);
+      state.set_pressed(HalaImGuiGamepadButton::DpadRight, state.is_pressed(HalaImGuiGamepadButton::DpadRight) || x > 0);
+    }
+    if has_axis(evdev::AbsoluteAxisType::ABS_HAT0Y) {
+      let y = abs[evdev::AbsoluteAxisType::ABS_HAT0Y.0 as usize].value;
+      state.set_pressed(HalaImGuiGamepadButton::DpadUp, state.is_pressed(HalaImGuiGamepadButton::DpadUp) || y < 0);
+      state.set_pressed(HalaImGuiGamepadButton::DpadDown, state.is_pressed(HalaImGuiGamepadButton::DpadDown) || y > 0);
+    }
+
+    Ok(Some(state))
+  }
+
+}
+
+/// The gamepad feeding the ImGUI navigation.
+pub(crate) struct HalaImGuiGamepad {
+  source: Option<Box<dyn HalaImGuiGamepadSource>>,
+  /// The deadzone of the sticks, from 0.0 to 1.0.
+  pub(crate) stick_deadzone: f32,
+  /// The deadzone of the triggers, from 0.0 to 1.0.
+  pub(crate) trigger_deadzone: f32,
+  is_connected: bool,
+}
+
+/// The implementation of the gamepad.
+impl HalaImGuiGamepad {
+
+  /// The default deadzone of the sticks.
+  pub(crate) const DEFAULT_STICK_DEADZONE: f32 = 0.15;
+  /// The default deadzone of the triggers.
+  pub(crate) const DEFAULT_TRIGGER_DEADZONE: f32 = 0.1;
+
+  /// Create the gamepad without an input source.
+  /// return: The gamepad.
+  pub(crate) fn new() -> Self {
+    Self {
+      source: None,
+      stick_deadzone: Self::DEFAULT_STICK_DEADZONE,
+      trigger_deadzone: Self::DEFAULT_TRIGGER_DEADZONE,
+      is_connected: false,
+    }
+  }
+
+  /// Replace the input source, the keys of the old source are released at the next update.
+  /// param source: The gamepad input source, None to remove it.
+  pub(crate) fn set_source(&mut self, source: Option<Box<dyn HalaImGuiGamepadSource>>) {
+    self.source = source;
+  }
+
+  /// Poll the source and feed the gamepad keys to ImGUI.
+  /// param io: The ImGUI IO.
+  pub(crate) fn update(&mut self, io: &mut imgui::Io) {
+    if self.source.is_none() && !self.is_connected {
+      return;
+    }
+
+    let was_connected = self.is_connected;
+    let state = self.poll();
+    if self.is_connected != was_connected {
+      io.backend_flags.set(imgui::BackendFlags::HAS_GAMEPAD, self.is_connected);
+    }
+
+    // Release all keys after disconnected, ImGUI filters the duplicated events.
+    for (key, down, value) in self.key_events(&state.unwrap_or_default()) {
+      // imgui-rs has no wrapper of the analog key events.
+      unsafe {
+        imgui::sys::ImGuiIO_AddKeyAnalogEvent(io.raw_mut(), key as imgui::sys::ImGuiKey, down, value);
+      }
+    }
+  }
+
+  /// Poll the source and track the connection.
+  /// return: The state, None if the gamepad is disconnected or removed.
+  fn poll(&mut self) -> Option<HalaImGuiGamepadState> {
+    let state = match self.source.as_mut().map_or(Ok(None), |source| source.poll()) {
+      Ok(state) => state,
+      Err(e) => {
+        if self.is_connected {
+          log::warn!("Failed to poll the gamepad: {}", e);
+        }
+        None
+      },
+    };
+
+    if state.is_some() != self.is_connected {
+      log::debug!("Gamepad {}.", if state.is_some() { "connected" } else { "disconnected" });
+      self.is_connected = state.is_some();
+    }
+
+    state
+  }
+
+  /// Map the gamepad state to the ImGUI key events.
+  /// param state: The gamepad state.
+  /// return: The key events of (key, down, analog value).
+  fn key_events(&self, state: &HalaImGuiGamepadState) -> Vec<(imgui::Key, bool, f32)> {
+    let mut events = Vec::with_capacity(HalaImGuiGamepadButton::COUNT + 8);
+    for button in HalaImGuiGamepadButton::ALL {
+      match button {
+        HalaImGuiGamepadButton::L2 | HalaImGuiGamepadButton::R2 => (),
+        _ => {
+          let is_pressed = state.is_pressed(button);
+          events.push((button.to_key(), is_pressed, if is_pressed { 1.0 } else { 0.0 }));
+        },
+      }
+    }
+
+    let trigger = |axis, button| {
+      let value = Self::apply_deadzone(state.get_axis(axis), self.trigger_deadzone);
+      if state.is_pressed(button) { 1.0 } else { value }
+    };
+    let stick = |axis| Self::apply_deadzone(state.get_axis(axis), self.stick_deadzone);
+    let neg_stick = |axis| Self::apply_deadzone(-state.get_axis(axis), self.stick_deadzone);
+    for (key, value) in [
+      (imgui::Key::GamepadL2, trigger(HalaImGuiGamepadAxis::LeftTrigger, HalaImGuiGamepadButton::L2)),
+      (imgui::Key::GamepadR2, trigger(HalaImGuiGamepadAxis::RightTrigger, HalaImGuiGamepadButton::R2)),
+      (imgui::Key::GamepadLStickLeft, neg_stick(HalaImGuiGamepadAxis::LeftStickX)),
+      (imgui::Key::GamepadLStickRight, stick(HalaImGuiGamepadAxis::LeftStickX)),
+      (imgui::Key::GamepadLStickUp, neg_stick(HalaImGuiGamepadAxis::LeftStickY)),
+      (imgui::Key::GamepadLStickDown, stick(HalaImGuiGamepadAxis::LeftStickY)),
+      (imgui::Key::GamepadRStickLeft, neg_stick(HalaImGuiGamepadAxis::RightStickX)),
+      (imgui::Key::GamepadRStickRight, stick(HalaImGuiGamepadAxis::RightStickX)),
+      (imgui::Key::GamepadRStickUp, neg_stick(HalaImGuiGamepadAxis::RightStickY)),
+      (imgui::Key::GamepadRStickDown, stick(HalaImGuiGamepadAxis::RightStickY)),
+    ] {
+      events.push((key, value > 0.0, value));
+    }
+
+    events
+  }
+
+  /// Remap the value out of the deadzone to 0.0 to 1.0, the negative values are 0.0.
+  /// param value: The value.
+  /// param deadzone: The deadzone.
+  /// return: The remapped value.
+  fn apply_deadzone(value: f32, deadzone: f32) -> f32 {
+    if value <= deadzone {
+      return 0.0;
+    }
+    ((value - deadzone) / (1.0 - deadzone).max(f32::EPSILON)).min(1.0)
+  }
+
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  /// Get the event of the key.
+  fn find_event(events: &[(imgui::Key, bool, f32)], key: imgui::Key) -> (bool, f32) {
+    events.iter().find(|(k, _, _)| *k == key).map(|&(_, down, value)| (down, value)).unwrap()
+  }
+
+  #[test]
+  fn fake_gamepad_tracks_connection() {
+    let fake = HalaImGuiFakeGamepad::new();
+    let mut gamepad = HalaImGuiGamepad::new();
+    gamepad.set_source(Some(Box::new(fake.clone())));
+
+    assert!(gamepad.poll().is_some());
+    assert!(gamepad.is_connected);
+
+    fake.disconnect();
+    assert!(gamepad.poll().is_none());
+    assert!(!gamepad.is_connected);
+
+    fake.set_pressed(HalaImGuiGamepadButton::FaceDown, true);
+    assert!(gamepad.poll().is_some_and(|state| state.is_pressed(HalaImGuiGamepadButton::FaceDown)));
+    assert!(gamepad.is_connected);
+  }
+
+  #[test]
+  fn fake_gamepad_maps_buttons() {
+    let fake = HalaImGuiFakeGamepad::new();
+    let mut gamepad = HalaImGuiGamepad::new();
+    gamepad.set_source(Some(Box::new(fake.clone())));
+
+    fake.set_pressed(HalaImGuiGamepadButton::FaceDown, true);
+    fake.set_pressed(HalaImGuiGamepadButton::L2, true);
+    let events = gamepad.key_events(&gamepad.poll().unwrap());
+
+    assert_eq!(find_event(&events, imgui::Key::GamepadFaceDown), (true, 1.0));
+    assert_eq!(find_event(&events, imgui::Key::GamepadFaceUp), (false, 0.0));
+    // The digital trigger is sent as the fully pressed analog trigger.
+    assert_eq!(find_event(&events, imgui::Key::GamepadL2), (true, 1.0));
+    assert_eq!(find_event(&events, imgui::Key::GamepadR2), (false, 0.0));
+    assert_eq!(events.iter().filter(|(key, _, _)| *key == imgui::Key::GamepadL2).count(), 1);
+  }
+
+  #[test]
+  fn fake_gamepad_applies_deadzones() {
+    let fake = HalaImGuiFakeGamepad::new();
+    let mut gamepad = HalaImGuiGamepad::new();
+    gamepad.set_source(Some(Box::new(fake.clone())));
+    gamepad.stick_deadzone = 0.2;
+    gamepad.trigger_deadzone = 0.5;
+
+    fake.set_axis(HalaImGuiGamepadAxis::LeftStickX, -0.6);
+    fake.set_axis(HalaImGuiGamepadAxis::LeftStickY, 0.1);
+    fake.set_axis(HalaImGuiGamepadAxis::RightTrigger, 0.75);
+    let events = gamepad.key_events(&gamepad.poll().unwrap());
+
+    let (down, value) = find_event(&events, imgui::Key::GamepadLStickLeft);
+    assert!(down);
+    assert!((value - 0.5).abs() < 1e-6);
+    assert_eq!(find_event(&events, imgui::Key::GamepadLStickRight), (false, 0.0));
+    // Inside the deadzone.
+    assert_eq!(find_event(&events, imgui::Key::GamepadLStickDown), (false, 0.0));
+    let (down, value) = find_event(&events, imgui::Key::GamepadR2);
+    assert!(down);
+    assert!((value - 0.5).abs() < 1e-6);
+  }
+
+  #[test]
+  fn fake_gamepad_releases_keys_after_disconnected() {
+    let fake = HalaImGuiFakeGamepad::new();
+    let mut gamepad = HalaImGuiGamepad::new();
+    gamepad.set_source(Some(Box::new(fake.clone())));
+
+    fake.set_pressed(HalaImGuiGamepadButton::Start, true);
+    fake.set_axis(HalaImGuiGamepadAxis::RightStickY, -1.0);
+    assert!(gamepad.poll().is_some());
+
+    fake.disconnect();
+    let events = gamepad.key_events(&gamepad.poll().unwrap_or_default());
+    assert!(events.iter().all(|&(_, down, value)| !down && value == 0.0));
+  }
+
+  #[test]
+  fn deadzone_remaps_to_unit_range() {
+    assert_eq!(HalaImGuiGamepad::apply_deadzone(0.1, 0.2), 0.0);
+    assert_eq!(HalaImGuiGamepad::apply_deadzone(-1.0, 0.2), 0.0);
+    assert_eq!(HalaImGuiGamepad::apply_deadzone(1.0, 0.2), 1.0);
+    assert_eq!(HalaImGuiGamepad::apply_deadzone(1.0, 1.0), 0.0);
+    assert!((HalaImGuiGamepad::apply_deadzone(0.6, 0.2) - 0.5).abs() < 1e-6);
+  }
+
+}
@@ -0,0 +1,541 @@
+use std::{
+  collections::HashMap,
+  path::Path,
+};
+
+use anyhow::{Result, anyhow};
+
+use crate::clipboard::{HalaImGuiClipboard, HalaImGuiMemoryClipboard};
+use crate::imgui::{FONT_TEXTURE_ID, HalaImGuiBlendMode};
+
+/// The number of frames built before the captured one.
+/// Auto-sized windows are hidden in their first frame, so the layout needs a few frames to settle.
+const WARMUP_FRAMES: usize = 2;
+
+/// The environment variable which forces the snapshots to be written instead of compared.
+const UPDATE_SNAPSHOTS_ENV: &str = "HALA_IMGUI_UPDATE_SNAPSHOTS";
+
+/// The headless ImGUI context.
+/// It builds the frames at a fixed size and rasterizes the draw data on the CPU,
+/// so the UI can be tested without a GPU and a window.
+/// NOTICE: ImGUI allows only one active context at a time, run the tests using it with --test-threads=1.
+pub struct HalaImGuiHeadless {
+  width: u32,
+  height: u32,
+  clear_color: [f32; 4],
+
+  font_texture: image::RgbaImage,
+  /// The user textures and the alpha modes of their texels.
+  textures: HashMap<usize, (image::RgbaImage, HalaImGuiBlendMode)>,
+  next_texture_id: usize,
+
+  imgui: imgui::Context,
+  clipboard: HalaImGuiClipboard,
+}
+
+/// The implementation of the headless ImGUI context.
+impl HalaImGuiHeadless {
+
+  /// Create a new headless ImGUI context.
+  /// param width: The width of the frame.
+  /// param height: The height of the frame.
+  /// return: The headless ImGUI context.
+  pub fn new(width: u32, height: u32) -> Self {
+    let mut imgui = imgui::Context::create();
+    imgui.style_mut().use_dark_colors();
+    imgui.set_ini_filename(None);
+    imgui.io_mut().config_flags = imgui::ConfigFlags::NAV_ENABLE_KEYBOARD;
+    imgui.io_mut().backend_flags |= imgui::BackendFlags::RENDERER_HAS_VTX_OFFSET;
+    // The tests must not touch the clipboard of the desktop session.
+    let clipboard = HalaImGuiClipboard::new(HalaImGuiMemoryClipboard::default());
+    imgui.set_clipboard_backend(clipboard.clone());
+    let font_texture = Self::build_font_texture(&mut imgui);
+
+    log::debug!("Headless ImGUI context created with size {}x{}.", width, height);
+    Self {
+      width,
+      height,
+      clear_color: [0.0, 0.0, 0.0, 1.0],
+      font_texture,
+      textures: HashMap::new(),
+      next_texture_id: FONT_TEXTURE_ID + 1,
+      imgui,
+      clipboard,
+    }
+  }
+
+  /// Get the ImGUI context, e.g. to change the style, the fonts or to feed the input events.
+  /// The font atlas is rasterized again in the next render when the fonts are changed.
+  /// return: The ImGUI context.
+  pub fn imgui_mut(&mut self) -> &mut imgui::Context {
+    &mut self.imgui
+  }
+
+  /// Get the clipboard text.
+  /// return: The text, None if the clipboard is empty.
+  pub fn get_clipboard_text(&self) -> Option<String> {
+    self.clipboard.get_text()
+  }
+
+  /// Set the clipboard text.
+  /// param text: The text.
+  pub fn set_clipboard_text(&self, text: &str) {
+    self.clipboard.set_text(text);
+  }
+
+  /// Set the color which the frame is cleared to.
+  /// param clear_color: The clear color.
+  pub fn set_clear_color(&mut self, clear_color: [f32; 4]) {
+    self.clear_color = clear_color;
+  }
+
+  /// Register a user texture.
+  /// param image: The image.
+  /// return: The texture ID used by imgui::Ui::image and friends.
+  pub fn register_texture(&mut self, image: image::RgbaImage) -> imgui::TextureId {
+    self.register_texture_with_alpha_mode(image, HalaImGuiBlendMode::Straight)
+  }
+
+  /// Register a user texture with the alpha mode of its texels.
+  /// param image: The image.
+  /// param alpha_mode: The alpha mode of the texels, which selects the blending like the pipelines of HalaImGui.
+  /// return: The texture ID used by imgui::Ui::image and friends.
+  pub fn register_texture_with_alpha_mode(&mut self, image: image::RgbaImage, alpha_mode: HalaImGuiBlendMode) -> imgui::TextureId {
+    let texture_id = self.next_texture_id;
+    self.next_texture_id += 1;
+    self.textures.insert(texture_id, (image, alpha_mode));
+
+    imgui::TextureId::new(texture_id)
+  }
+
+  /// Build a frame and rasterize it.
+  /// The UI function is called for a few warmup frames before the captured one, so it must be repeatable.
+  /// param ui_fn: The UI function.
+  /// return: The rasterized frame.
+  pub fn render<F>(&mut self, mut ui_fn: F) -> Result<image::RgbaImage>
+    where F: FnMut(&mut imgui::Ui) -> Result<()>
+  {
+    for _ in 0..WARMUP_FRAMES {
+      self.build_frame(&mut ui_fn)?;
+    }
+    self.build_frame(&mut ui_fn)?;
+
+    let mut pixels = vec![self.clear_color; (self.width * self.height) as usize];
+    let draw_data = unsafe {
+      let draw_data = imgui::sys::igGetDrawData() as *mut imgui::DrawData;
+      if draw_data.is_null() {
+        return Err(anyhow!("The headless ImGUI frame has no draw data."));
+      }
+      &*draw_data
+    };
+    self.rasterize(draw_data, &mut pixels);
+
+    let mut frame = image::RgbaImage::new(self.width, self.height);
+    for (pixel, color) in frame.pixels_mut().zip(pixels.iter()) {
+      *pixel = image::Rgba(color.map(|c| (c.clamp(0.0, 1.0) * 255.0 + 0.5) as u8));
+    }
+
+    Ok(frame)
+  }
+
+  /// Compare the frame with the snapshot image.
+  /// The snapshot is written instead when it does not exist or HALA_IMGUI_UPDATE_SNAPSHOTS is set.
+  /// On mismatch, the actual frame and the difference are saved beside the snapshot as *.actual.png and *.diff.png.
+  /// param frame: The rasterized frame.
+  /// param path: The path of the PNG snapshot.
+  /// param tolerance: The maximum difference allowed per channel of a pixel.
+  /// return: The result, error if any pixel differs more than the tolerance.
+  pub fn assert_snapshot<P: AsRef<Path>>(frame: &image::RgbaImage, path: P, tolerance: u8) -> Result<()> {
+    let path = path.as_ref();
+    if !path.exists() || std::env::var_os(UPDATE_SNAPSHOTS_ENV).is_some() {
+      if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent)?;
+      }
+      frame.save(path)?;
+      log::info!("Snapshot \"{}\" written.", path.display());
+      return Ok(());
+    }
+
+    let snapshot = image::open(path)?.into_rgba8();
+    if snapshot.dimensions() != frame.dimensions() {
+      return Err(anyhow!(
+        "The frame size {:?} does not match the snapshot \"{}\" size {:?}.",
+        frame.dimensions(), path.display(), snapshot.dimensions()
+      ));
+    }
+
+    let mut num_of_mismatches = 0;
+    let mut diff = image::RgbaImage::new(frame.width(), frame.height());
+    for ((actual, expected), diff) in frame.pixels().zip(snapshot.pixels()).zip(diff.pixels_mut()) {
+      let is_mismatch = actual.0.iter().zip(expected.0.iter()).any(|(a, e)| a.abs_diff(*e) > tolerance);
+      if is_mismatch {
+        num_of_mismatches += 1;
+        *diff = image::Rgba([255, 0, 0, 255]);
+      } else {
+        let luma = ((expected[0] as u32 + expected[1] as u32 + expected[2] as u32) / 12) as u8;
+        *diff = image::Rgba([luma, luma, luma, 255]);
+      }
+    }
+    if num_of_mismatches > 0 {
+      let actual_path = path.with_extension("actual.png");
+      let diff_path = path.with_extension("diff.png");
+      frame.save(&actual_path)?;
+      diff.save(&diff_path)?;
+      return Err(anyhow!(
+        "{} pixels differ from the snapshot \"{}\" by more than {}, see \"{}\" and \"{}\".",
+        num_of_mismatches, path.display(), tolerance, actual_path.display(), diff_path.display()
+      ));
+    }
+
+    Ok(())
+  }
+
+  /// Build one frame.
+  /// param ui_fn: The UI function.
+  /// return: The result.
+  fn build_frame<F>(&mut self, ui_fn: &mut F) -> Result<()>
+    where F: FnMut(&mut imgui::Ui) -> Result<()>
+  {
+    let io = self.imgui.io_mut();
+    io.delta_time = 1.0 / 60.0;
+    io.display_size = [self.width as f32, self.height as f32];
+    io.display_framebuffer_scale = [1.0, 1.0];
+
+    // The fonts may be changed through imgui_mut since the last frame.
+    let fonts = self.imgui.fonts();
+    if !fonts.is_built() || fonts.tex_id.id() != FONT_TEXTURE_ID {
+      log::debug!("Headless ImGUI font atlas rebuilt.");
+      self.font_texture = Self::build_font_texture(&mut self.imgui);
+    }
+
+    ui_fn(self.imgui.new_frame())?;
+    self.imgui.render();
+
+    Ok(())
+  }
+
+  /// Build the font atlas and copy it to the CPU texture.
+  /// param imgui: The ImGUI context.
+  /// return: The font texture.
+  fn build_font_texture(imgui: &mut imgui::Context) -> image::RgbaImage {
+    let font_texture = {
+      let font_texture = imgui.fonts().build_rgba32_texture();
+      image::RgbaImage::from_raw(font_texture.width, font_texture.height, font_texture.data.to_vec())
+        .expect("The font atlas size does not match its data.")
+    };
+    imgui.fonts().tex_id = imgui::TextureId::new(FONT_TEXTURE_ID);
+
+    font_texture
+  }
+
+  /// Rasterize the draw data.
+  /// It follows the Vulkan pipelines: vertex color times the texture sample, blended by the source alpha,
+  /// or by one after premultiplying the vertex color for the premultiplied alpha textures.
+  /// param draw_data: The draw data.
+  /// param pixels: The pixels of the frame.
+  fn rasterize(&self, draw_data: &imgui::DrawData, pixels: &mut [[f32; 4]]) {
+    let clip_off = draw_data.display_pos;
+    let clip_scale = draw_data.framebuffer_scale;
+
+    for cmd_list in draw_data.draw_lists() {
+      let vtx_buffer = cmd_list.vtx_buffer();
+      let idx_buffer = cmd_list.idx_buffer();
+      for cmd in cmd_list.commands() {
+        if let imgui::DrawCmd::Elements { count, cmd_params } = cmd {
+          let clip_rect = [
+            ((cmd_params.clip_rect[0] - clip_off[0]) * clip_scale[0]).max(0.0),
+            ((cmd_params.clip_rect[1] - clip_off[1]) * clip_scale[1]).max(0.0),
+            ((cmd_params.clip_rect[2] - clip_off[0]) * clip_scale[0]).min(self.width as f32),
+            ((cmd_params.clip_rect[3] - clip_off[1]) * clip_scale[1]).min(self.height as f32),
+          ];
+          if clip_rect[2] <= clip_rect[0] || clip_rect[3] <= clip_rect[1] {
+            continue;
+          }
+
+          // Unknown texture IDs fall back to the font atlas.
+          let (texture, alpha_mode) = self.textures.get(&cmd_params.texture_id.id())
+            .map_or((&self.font_texture, HalaImGuiBlendMode::Straight), |(texture, alpha_mode)| (texture, *alpha_mode));
+          let indices = &idx_buffer[cmd_params.idx_offset..cmd_params.idx_offset + count];
+          for triangle in indices.chunks_exact(3) {
+            let vertices = [
+              &vtx_buffer[cmd_params.vtx_offset + triangle[0] as usize],
+              &vtx_buffer[cmd_params.vtx_offset + triangle[1] as usize],
+              &vtx_buffer[cmd_params.vtx_offset + triangle[2] as usize],
+            ];
+            self.rasterize_triangle(vertices, clip_off, clip_scale, &clip_rect, texture, alpha_mode, pixels);
+          }
+        }
+      }
+    }
+  }
+
+  /// Rasterize a triangle.
+  /// param vertices: The vertices.
+  /// param clip_off: The display position of the draw data.
+  /// param clip_scale: The framebuffer scale of the draw data.
+  /// param clip_rect: The clip rectangle(min x, min y, max x, max y) in framebuffer space.
+  /// param texture: The texture.
+  /// param alpha_mode: The alpha mode of the texture.
+  /// param pixels: The pixels of the frame.
+  #[allow(clippy::too_many_arguments)]
+  fn rasterize_triangle(
+    &self,
+    vertices: [&imgui::DrawVert; 3],
+    clip_off: [f32; 2],
+    clip_scale: [f32; 2],
+    clip_rect: &[f32; 4],
+    texture: &image::RgbaImage,
+    alpha_mode: HalaImGuiBlendMode,
+    pixels: &mut [[f32; 4]],
+  ) {
+    let mut positions = vertices.map(|v| [
+      (v.pos[0] - clip_off[0]) * clip_scale[0],
+      (v.pos[1] - clip_off[1]) * clip_scale[1],
+    ]);
+    let mut vertices = vertices;
+    let mut area = Self::edge(positions[0], positions[1], positions[2]);
+    if area == 0.0 {
+      return;
+    }
+    // Make all triangles the same winding, so the shared edges are walked in opposite directions.
+    if area < 0.0 {
+      positions.swap(1, 2);
+      vertices.swap(1, 2);
+      area = -area;
+    }
+
+    let min_x = positions.iter().map(|p| p[0]).fold(clip_rect[2], f32::min).max(clip_rect[0]).floor() as u32;
+    let min_y = positions.iter().map(|p| p[1]).fold(clip_rect[3], f32::min).max(clip_rect[1]).floor() as u32;
+    let max_x = positions.iter().map(|p| p[0]).fold(clip_rect[0], f32::max).min(clip_rect[2]).ceil() as u32;
+    let max_y = positions.iter().map(|p| p[1]).fold(clip_rect[1], f32::max).min(clip_rect[3]).ceil() as u32;
+
+    let edges = [(1, 2), (2, 0), (0, 1)];
+    for y in min_y..max_y {
+      for x in min_x..max_x {
+        let p = [x as f32 + 0.5, y as f32 + 0.5];
+        if p[0] < clip_rect[0] || p[0] >= clip_rect[2] || p[1] < clip_rect[1] || p[1] >= clip_rect[3] {
+          continue;
+        }
+
+        let mut weights = [0.0f32; 3];
+        let mut is_inside = true;
+        for (i, (a, b)) in edges.iter().enumerate() {
+          let w = Self::edge(positions[*a], positions[*b], p);
+          // Top-left rule: the pixels exactly on a shared edge belong to only one of the triangles.
+          let dx = positions[*b][0] - positions[*a][0];
+          let dy = positions[*b][1] - positions[*a][1];
+          if w < 0.0 || (w == 0.0 && !(dy > 0.0 || (dy == 0.0 && dx < 0.0))) {
+            is_inside = false;
+            break;
+          }
+          weights[i] = w / area;
+        }
+        if !is_inside {
+          continue;
+        }
+
+        let mut uv = [0.0f32; 2];
+        let mut color = [0.0f32; 4];
+        for (weight, vertex) in weights.iter().zip(vertices.iter()) {
+          uv[0] += weight * vertex.uv[0];
+          uv[1] += weight * vertex.uv[1];
+          for (c, v) in color.iter_mut().zip(vertex.col.iter()) {
+            *c += weight * (*v as f32 / 255.0);
+          }
+        }
+        // The premultiplied alpha pipeline premultiplies the straight alpha vertex colors.
+        if alpha_mode == HalaImGuiBlendMode::Premultiplied {
+          color[0] *= color[3];
+          color[1] *= color[3];
+          color[2] *= color[3];
+        }
+        let texel = Self::sample(texture, uv);
+        let src = [
+          color[0] * texel[0],
+          color[1] * texel[1],
+          color[2] * texel[2],
+          color[3] * texel[3],
+        ];
+
+        // Color: SRC_ALPHA or ONE for the premultiplied alpha, ONE_MINUS_SRC_ALPHA. Alpha: ONE, ONE_MINUS_SRC_ALPHA.
+        let src_factor = match alpha_mode {
+          HalaImGuiBlendMode::Straight => src[3],
+          HalaImGuiBlendMode::Premultiplied => 1.0,
+        };
+        let dst = &mut pixels[(y * self.width + x) as usize];
+        dst[0] = src[0] * src_factor + dst[0] * (1.0 - src[3]);
+        dst[1] = src[1] * src_factor + dst[1] * (1.0 - src[3]);
+        dst[2] = src[2] * src_factor + dst[2] * (1.0 - src[3]);
+        dst[3] = src[3] + dst[3] * (1.0 - src[3]);
+      }
+    }
+  }
+
+  /// The edge function of the point against the edge from a to b.
+  /// param a: The start of the edge.
+  /// param b: The end of the edge.
+  /// param p: The point.
+  /// return: The doubled signed area of the triangle (a, b, p).
+  fn edge(a: [f32; 2], b: [f32; 2], p: [f32; 2]) -> f32 {
+    (b[0] - a[0]) * (p[1] - a[1]) - (b[1] - a[1]) * (p[0] - a[0])
+  }
+
+  /// Sample the texture with the bilinear filter and the repeat addressing, the same as the default texture sampler.
+  /// param texture: The texture.
+  /// param uv: The texture coordinate.
+  /// return: The color.
+  fn sample(texture: &image::RgbaImage, uv: [f32; 2]) -> [f32; 4] {
+    let (width, height) = texture.dimensions();
+    let x = uv[0] * width as f32 - 0.5;
+    let y = uv[1] * height as f32 - 0.5;
+    let (x0, y0) = (x.floor(), y.floor());
+    let (fx, fy) = (x - x0, y - y0);
+
+    let texel = |tx: f32, ty: f32| -> [f32; 4] {
+      let tx = (tx as i64).rem_euclid(width as i64) as u32;
+      let ty = (ty as i64).rem_euclid(height as i64) as u32;
+      texture.get_pixel(tx, ty).0.map(|c| c as f32 / 255.0)
+    };
+    let t00 = texel(x0, y0);
+    let t10 = texel(x0 + 1.0, y0);
+    let t01 = texel(x0, y0 + 1.0);
+    let t11 = texel(x0 + 1.0, y0 + 1.0);
+
+    let mut color = [0.0f32; 4];
+    for (i, c) in color.iter_mut().enumerate() {
+      let top = t00[i] + (t10[i] - t00[i]) * fx;
+      let bottom = t01[i] + (t11[i] - t01[i]) * fx;
+      *c = top + (bottom - top) * fy;
+    }
+    color
+  }
+
+}
+
+#[cfg(test)]
+mod tests {
+  use std::sync::Mutex;
+
+  use super::*;
+
+  /// ImGUI allows only one active context at a time, so the tests creating one run in turn.
+  static CONTEXT_LOCK: Mutex<()> = Mutex::new(());
+
+  /// Render a window with a button filling the frame.
+  fn render_window(headless: &mut HalaImGuiHeadless) -> image::RgbaImage {
+    headless.render(|ui| {
+      ui.window("Snapshot")
+        .position([0.0, 0.0], imgui::Condition::Always)
+        .size([160.0, 80.0], imgui::Condition::Always)
+        .build(|| {
+          ui.button("Button");
+        });
+
+      Ok(())
+    }).expect("Failed to render the headless frame.")
+  }
+
+  #[test]
+  fn render_matches_snapshot() {
+    let _lock = CONTEXT_LOCK.lock().unwrap_or_else(|e| e.into_inner());
+    let mut headless = HalaImGuiHeadless::new(160, 80);
+    let frame = render_window(&mut headless);
+    assert_eq!(frame.dimensions(), (160, 80));
+    assert!(frame.pixels().any(|pixel| pixel.0 != [0, 0, 0, 255]));
+
+    let path = std::env::temp_dir().join(format!("hala_imgui_headless_{}.png", std::process::id()));
+    let _ = std::fs::remove_file(&path);
+    // The first call writes the snapshot, the second one compares the same frame rendered again.
+    HalaImGuiHeadless::assert_snapshot(&frame, &path, 0).unwrap();
+    HalaImGuiHeadless::assert_snapshot(&render_window(&mut headless), &path, 0).unwrap();
+
+    if std::env::var_os(UPDATE_SNAPSHOTS_ENV).is_none() {
+      let mut changed = frame.clone();
+      changed.put_pixel(80, 40, image::Rgba([255, 0, 255, 255]));
+      assert!(HalaImGuiHeadless::assert_snapshot(&changed, &path, 8).is_err());
+      let _ = std::fs::remove_file(path.with_extension("actual.png"));
+      let _ = std::fs::remove_file(path.with_extension("diff.png"));
+    }
+    let _ = std::fs::remove_file(&path);
+  }
+
+  /// Render a texture tinted with half alpha over the black background.
+  fn render_tinted_texture(headless: &mut HalaImGuiHeadless, texture_id: imgui::TextureId) -> image::RgbaImage {
+    headless.render(|ui| {
+      ui.get_background_draw_list()
+        .add_image(texture_id, [8.0, 8.0], [24.0, 24.0])
+        .col([1.0, 1.0, 1.0, 0.5])
+        .build();
+
+      Ok(())
+    }).expect("Failed to render the headless frame.")
+  }
+
+  #[test]
+  fn premultiplied_texture_fades_with_tint_alpha() {
+    let _lock = CONTEXT_LOCK.lock().unwrap_or_else(|e| e.into_inner());
+    let mut headless = HalaImGuiHeadless::new(32, 32);
+    let straight = headless.register_texture(image::RgbaImage::from_pixel(4, 4, image::Rgba([255, 0, 0, 128])));
+    let premultiplied = headless.register_texture_with_alpha_mode(
+      image::RgbaImage::from_pixel(4, 4, image::Rgba([128, 0, 0, 128])),
+      HalaImGuiBlendMode::Premultiplied,
+    );
+
+    let straight_frame = render_tinted_texture(&mut headless, straight);
+    let premultiplied_frame = render_tinted_texture(&mut headless, premultiplied);
+
+    // Red 1.0 at alpha 0.5 * 0.5 over black, the premultiplied texels must fade the same.
+    let expected = (255.0f32 * 128.0 / 255.0 * 0.5).round() as u8;
+    for frame in [&straight_frame, &premultiplied_frame] {
+      let pixel = frame.get_pixel(16, 16);
+      assert!(pixel[0].abs_diff(expected) <= 1, "{:?} is not faded to {}", pixel, expected);
+      assert_eq!(frame.get_pixel(2, 2).0, [0, 0, 0, 255]);
+    }
+  }
+
+  #[test]
+  fn render_draw_list_over_16_bit_indices() {
+    let _lock = CONTEXT_LOCK.lock().unwrap_or_else(|e| e.into_inner());
+    let mut headless = HalaImGuiHeadless::new(64, 64);
+    let frame = headless.render(|ui| {
+      let draw_list = ui.get_background_draw_list();
+      // Enough rectangles to overflow the 16-bit indices of one draw list, the last one is drawn after the overflow.
+      for _ in 0..20000 {
+        draw_list.add_rect([0.0, 0.0], [4.0, 4.0], [0.0, 1.0, 0.0, 1.0]).filled(true).build();
+      }
+      draw_list.add_rect([40.0, 40.0], [56.0, 56.0], [1.0, 0.0, 0.0, 1.0]).filled(true).build();
+
+      Ok(())
+    }).expect("Failed to render the headless frame.");
+
+    let draw_data = unsafe { &*(imgui::sys::igGetDrawData() as *const imgui::DrawData) };
+    let cmd_list = draw_data.draw_lists().find(|cmd_list| cmd_list.vtx_buffer().len() > u16::MAX as usize + 1)
+      .expect("No draw list has more vertices than the 16-bit indices address.");
+    // With the 16-bit indices ImGUI splits the draw list with the vertex offsets, the 32-bit indices address it directly.
+    let is_split = cmd_list.commands().any(|cmd| matches!(cmd, imgui::DrawCmd::Elements { cmd_params, .. } if cmd_params.vtx_offset > 0));
+    assert_eq!(is_split, std::mem::size_of::<imgui::DrawIdx>() == 2);
+    assert_eq!(frame.get_pixel(48, 48).0, [255, 0, 0, 255]);
+    assert_eq!(frame.get_pixel(2, 2).0, [0, 255, 0, 255]);
+  }
+
+  #[test]
+  fn render_rebuilds_changed_fonts() {
+    let _lock = CONTEXT_LOCK.lock().unwrap_or_else(|e| e.into_inner());
+    let mut headless = HalaImGuiHeadless::new(160, 80);
+    let frame = render_window(&mut headless);
+
+    let fonts = headless.imgui_mut().fonts();
+    fonts.clear();
+    fonts.add_font(&[imgui::FontSource::DefaultFontData {
+      config: Some(imgui::FontConfig {
+        size_pixels: 26.0,
+        ..Default::default()
+      }),
+    }]);
+    let larger_frame = render_window(&mut headless);
+
+    assert_eq!(headless.imgui_mut().fonts().tex_id.id(), FONT_TEXTURE_ID);
+    assert_ne!(frame, larger_frame);
+  }
+
+}
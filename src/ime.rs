@@ -0,0 +1,74 @@
+/// The IME data of the text input which has the keyboard focus.
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub struct HalaImGuiImeData {
+  /// The top-left of the text caret in the logical pixels.
+  pub position: [f32; 2],
+  /// The line height of the text caret in the logical pixels.
+  pub line_height: f32,
+}
+
+/// The text being composed by the IME, rendered inline at the text caret.
+#[derive(Debug, Clone, Default)]
+pub(crate) struct HalaImGuiPreedit {
+  text: String,
+  /// The byte range of the IME cursor in the text, None to hide the cursor.
+  cursor: Option<(usize, usize)>,
+}
+
+/// The implementation of the preedit text.
+impl HalaImGuiPreedit {
+
+  /// Set the preedit text, an empty text ends the composition.
+  /// param text: The preedit text.
+  /// param cursor: The byte range of the IME cursor in the text.
+  pub(crate) fn set(&mut self, text: &str, cursor: Option<(usize, usize)>) {
+    self.text.clear();
+    self.text.push_str(text);
+    self.cursor = cursor;
+  }
+
+  /// Clear the preedit text.
+  pub(crate) fn clear(&mut self) {
+    self.text.clear();
+    self.cursor = None;
+  }
+
+  /// Whether the IME is composing.
+  /// return: Whether the preedit text is not empty.
+  pub(crate) fn is_composing(&self) -> bool {
+    !self.text.is_empty()
+  }
+
+  /// Draw the preedit text over the text input at the caret.
+  /// param ui: The UI of the current frame.
+  /// param ime_data: The IME data of the text input.
+  pub(crate) fn draw(&self, ui: &imgui::Ui, ime_data: &HalaImGuiImeData) {
+    if self.text.is_empty() {
+      return;
+    }
+
+    let [x, y] = ime_data.position;
+    let width = ui.calc_text_size(&self.text)[0];
+    let bottom = y + ime_data.line_height;
+    let text_color = ui.style_color(imgui::StyleColor::Text);
+    let [r, g, b, _] = ui.style_color(imgui::StyleColor::FrameBg);
+
+    let draw_list = ui.get_foreground_draw_list();
+    // Cover the characters after the caret, the preedit text is not part of the input buffer yet.
+    draw_list.add_rect([x, y], [x + width, bottom], [r, g, b, 1.0]).filled(true).build();
+    if let Some((start, end)) = self.cursor {
+      let start_x = x + self.text.get(..start).map_or(0.0, |text| ui.calc_text_size(text)[0]);
+      let end_x = x + self.text.get(..end).map_or(width, |text| ui.calc_text_size(text)[0]);
+      if end_x > start_x {
+        draw_list.add_rect([start_x, y], [end_x, bottom], ui.style_color(imgui::StyleColor::TextSelectedBg))
+          .filled(true)
+          .build();
+      } else {
+        draw_list.add_line([start_x, y], [start_x, bottom - 1.0], text_color).build();
+      }
+    }
+    draw_list.add_text([x, y], text_color, &self.text);
+    draw_list.add_line([x, bottom - 1.0], [x + width, bottom - 1.0], text_color).build();
+  }
+
+}
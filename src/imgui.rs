@@ -1,12 +1,24 @@
 use std::{
-  cell::RefCell,
+  cell::{Cell, RefCell},
+  collections::{BTreeSet, HashMap, VecDeque},
+  path::Path,
   rc::Rc
 };
 
-use anyhow::{Ok, Result};
+use anyhow::{Ok, Result, anyhow};
 
 use imgui::internal::{RawCast, RawWrapper};
+
+use crate::buffer::HalaImGuiBuffer;
+use crate::clipboard::HalaImGuiClipboard;
+use crate::error::HalaImGuiError;
+use crate::gamepad::{HalaImGuiGamepad, HalaImGuiGamepadSource};
+use crate::ime::{HalaImGuiImeData, HalaImGuiPreedit};
+use crate::stats::{HalaImGuiGpuTimer, HalaImGuiStats};
+use crate::texture::{HalaImGuiSamplers, HalaImGuiTextureOptions};
+use crate::font::{HalaImGuiFont, HalaImGuiFontSource, HalaImGuiGlyphRanges};
 use winit::event::MouseButton;
+use winit::window::CursorIcon;
 use winit::keyboard::{
   PhysicalKey,
   KeyCode,
@@ -79,59 +91,1069 @@ const __GLSL_SHADER_VERT_SPV: [u32; 324] = [
 #version 450 core
 layout(location = 0) out vec4 fColor;
 layout(set=0, binding=0) uniform sampler2D sTexture;
+layout(push_constant) uniform uPushConstant { layout(offset = 16) float uPaperWhite; layout(offset = 24) uint uFlags; } pc;
+layout(constant_id = 0) const bool PREMULTIPLY_COLOR = false;
 layout(location = 0) in struct { vec4 Color; vec2 UV; } In;
+vec3 linear_to_srgb(vec3 c)
+{
+    return mix(c * 12.92, 1.055 * pow(c, vec3(1.0 / 2.4)) - 0.055, greaterThan(c, vec3(0.0031308)));
+}
 void main()
 {
-    fColor = In.Color * texture(sTexture, In.UV.st);
+    vec4 tex = texture(sTexture, In.UV.st);
+    // Bit 0 of uFlags: the texture is sampled as linear colors, e.g. the float and the sRGB formats.
+    vec3 tex_rgb = (pc.uFlags & 1u) != 0u ? linear_to_srgb(tex.rgb) : tex.rgb;
+    vec3 color = In.Color.rgb;
+    // The premultiplied alpha pipeline premultiplies the straight alpha vertex colors.
+    if (PREMULTIPLY_COLOR) color *= In.Color.a;
+    fColor = vec4(color * tex_rgb, In.Color.a * tex.a);
 }
 */
-const __GLSL_SHADER_FRAG_SPV: [u32; 193] = [
-  0x07230203,0x00010000,0x00080001,0x0000001e,0x00000000,0x00020011,0x00000001,0x0006000b,
+const __GLSL_SHADER_FRAG_SPV: [u32; 478] = [
+  0x07230203,0x00010000,0x00070000,0x00000051,0x00000000,0x00020011,0x00000001,0x0006000b,
   0x00000001,0x4c534c47,0x6474732e,0x3035342e,0x00000000,0x0003000e,0x00000000,0x00000001,
-  0x0007000f,0x00000004,0x00000004,0x6e69616d,0x00000000,0x00000009,0x0000000d,0x00030010,
-  0x00000004,0x00000007,0x00030003,0x00000002,0x000001c2,0x00040005,0x00000004,0x6e69616d,
-  0x00000000,0x00040005,0x00000009,0x6c6f4366,0x0000726f,0x00030005,0x0000000b,0x00000000,
-  0x00050006,0x0000000b,0x00000000,0x6f6c6f43,0x00000072,0x00040006,0x0000000b,0x00000001,
-  0x00005655,0x00030005,0x0000000d,0x00006e49,0x00050005,0x00000016,0x78655473,0x65727574,
-  0x00000000,0x00040047,0x00000009,0x0000001e,0x00000000,0x00040047,0x0000000d,0x0000001e,
-  0x00000000,0x00040047,0x00000016,0x00000022,0x00000000,0x00040047,0x00000016,0x00000021,
-  0x00000000,0x00020013,0x00000002,0x00030021,0x00000003,0x00000002,0x00030016,0x00000006,
-  0x00000020,0x00040017,0x00000007,0x00000006,0x00000004,0x00040020,0x00000008,0x00000003,
-  0x00000007,0x0004003b,0x00000008,0x00000009,0x00000003,0x00040017,0x0000000a,0x00000006,
-  0x00000002,0x0004001e,0x0000000b,0x00000007,0x0000000a,0x00040020,0x0000000c,0x00000001,
-  0x0000000b,0x0004003b,0x0000000c,0x0000000d,0x00000001,0x00040015,0x0000000e,0x00000020,
-  0x00000001,0x0004002b,0x0000000e,0x0000000f,0x00000000,0x00040020,0x00000010,0x00000001,
-  0x00000007,0x00090019,0x00000013,0x00000006,0x00000001,0x00000000,0x00000000,0x00000000,
-  0x00000001,0x00000000,0x0003001b,0x00000014,0x00000013,0x00040020,0x00000015,0x00000000,
-  0x00000014,0x0004003b,0x00000015,0x00000016,0x00000000,0x0004002b,0x0000000e,0x00000018,
-  0x00000001,0x00040020,0x00000019,0x00000001,0x0000000a,0x00050036,0x00000002,0x00000004,
-  0x00000000,0x00000003,0x000200f8,0x00000005,0x00050041,0x00000010,0x00000011,0x0000000d,
-  0x0000000f,0x0004003d,0x00000007,0x00000012,0x00000011,0x0004003d,0x00000014,0x00000017,
-  0x00000016,0x00050041,0x00000019,0x0000001a,0x0000000d,0x00000018,0x0004003d,0x0000000a,
-  0x0000001b,0x0000001a,0x00050057,0x00000007,0x0000001c,0x00000017,0x0000001b,0x00050085,
-  0x00000007,0x0000001d,0x00000012,0x0000001c,0x0003003e,0x00000009,0x0000001d,0x000100fd,
-  0x00010038
+  0x0007000f,0x00000004,0x00000002,0x6e69616d,0x00000000,0x00000003,0x00000004,0x00030010,
+  0x00000002,0x00000007,0x00030003,0x00000002,0x000001c2,0x00040005,0x00000002,0x6e69616d,
+  0x00000000,0x00070005,0x00000005,0x656e696c,0x745f7261,0x72735f6f,0x76286267,0x003b3366,
+  0x00040005,0x00000003,0x6c6f4366,0x0000726f,0x00050005,0x00000006,0x78655473,0x65727574,
+  0x00000000,0x00030005,0x00000004,0x00006e49,0x00060005,0x00000007,0x73755075,0x6e6f4368,
+  0x6e617473,0x00000074,0x00060006,0x00000007,0x00000000,0x70615075,0x68577265,0x00657469,
+  0x00050006,0x00000007,0x00000001,0x616c4675,0x00007367,0x00030005,0x00000008,0x00006370,
+  0x00070005,0x00000009,0x4d455250,0x49544c55,0x5f594c50,0x4f4c4f43,0x00000052,0x00040047,
+  0x00000003,0x0000001e,0x00000000,0x00040047,0x00000006,0x00000022,0x00000000,0x00040047,
+  0x00000006,0x00000021,0x00000000,0x00040047,0x00000004,0x0000001e,0x00000000,0x00050048,
+  0x00000007,0x00000000,0x00000023,0x00000010,0x00050048,0x00000007,0x00000001,0x00000023,
+  0x00000018,0x00030047,0x00000007,0x00000002,0x00040047,0x00000009,0x00000001,0x00000000,
+  0x00020013,0x0000000a,0x00030021,0x0000000b,0x0000000a,0x00030016,0x0000000c,0x00000020,
+  0x00040015,0x0000000d,0x00000020,0x00000000,0x00040017,0x0000000e,0x0000000c,0x00000002,
+  0x00040017,0x0000000f,0x0000000c,0x00000003,0x00040017,0x00000010,0x0000000c,0x00000004,
+  0x00020014,0x00000011,0x00040017,0x00000012,0x00000011,0x00000003,0x00040021,0x00000013,
+  0x0000000f,0x0000000f,0x00040015,0x00000014,0x00000020,0x00000001,0x0004002b,0x00000014,
+  0x00000015,0x00000000,0x0004002b,0x00000014,0x00000016,0x00000001,0x0004002b,0x00000014,
+  0x00000017,0x00000002,0x0004002b,0x0000000d,0x00000018,0x00000000,0x0004002b,0x0000000d,
+  0x00000019,0x00000001,0x00040020,0x0000001a,0x00000003,0x00000010,0x0004003b,0x0000001a,
+  0x00000003,0x00000003,0x00090019,0x0000001b,0x0000000c,0x00000001,0x00000000,0x00000000,
+  0x00000000,0x00000001,0x00000000,0x0003001b,0x0000001c,0x0000001b,0x00040020,0x0000001d,
+  0x00000000,0x0000001c,0x0004001e,0x0000001e,0x00000010,0x0000000e,0x00040020,0x0000001f,
+  0x00000001,0x0000001e,0x0004003b,0x0000001f,0x00000004,0x00000001,0x00040020,0x00000020,
+  0x00000001,0x00000010,0x00040020,0x00000021,0x00000001,0x0000000e,0x0004002b,0x0000000c,
+  0x00000022,0x414eb852,0x0004002b,0x0000000c,0x00000023,0x3d6147ae,0x0004002b,0x0000000c,
+  0x00000024,0x3f870a3d,0x0006002c,0x0000000f,0x00000025,0x00000022,0x00000022,0x00000022,
+  0x0006002c,0x0000000f,0x00000026,0x00000023,0x00000023,0x00000023,0x0006002c,0x0000000f,
+  0x00000027,0x00000024,0x00000024,0x00000024,0x0004002b,0x0000000c,0x00000028,0x3ed55555,
+  0x0004002b,0x0000000c,0x00000029,0x3b4d2e1c,0x0006002c,0x0000000f,0x0000002a,0x00000028,
+  0x00000028,0x00000028,0x0006002c,0x0000000f,0x0000002b,0x00000029,0x00000029,0x00000029,
+  0x00030031,0x00000011,0x00000009,0x0004003b,0x0000001d,0x00000006,0x00000000,0x0004001e,
+  0x00000007,0x0000000c,0x0000000d,0x00040020,0x0000002c,0x00000009,0x00000007,0x0004003b,
+  0x0000002c,0x00000008,0x00000009,0x00040020,0x0000002d,0x00000009,0x0000000c,0x00040020,
+  0x0000002e,0x00000009,0x0000000d,0x00050036,0x0000000a,0x00000002,0x00000000,0x0000000b,
+  0x000200f8,0x0000002f,0x00050041,0x00000021,0x00000030,0x00000004,0x00000016,0x0004003d,
+  0x0000000e,0x00000031,0x00000030,0x0004003d,0x0000001c,0x00000032,0x00000006,0x00050057,
+  0x00000010,0x00000033,0x00000032,0x00000031,0x00050041,0x00000020,0x00000034,0x00000004,
+  0x00000015,0x0004003d,0x00000010,0x00000035,0x00000034,0x0008004f,0x0000000f,0x00000036,
+  0x00000035,0x00000035,0x00000000,0x00000001,0x00000002,0x00050051,0x0000000c,0x00000037,
+  0x00000035,0x00000003,0x0008004f,0x0000000f,0x00000038,0x00000033,0x00000033,0x00000000,
+  0x00000001,0x00000002,0x00050051,0x0000000c,0x00000039,0x00000033,0x00000003,0x00050041,
+  0x0000002e,0x0000003a,0x00000008,0x00000016,0x0004003d,0x0000000d,0x0000003b,0x0000003a,
+  0x000500c7,0x0000000d,0x0000003c,0x0000003b,0x00000019,0x000500ab,0x00000011,0x0000003d,
+  0x0000003c,0x00000018,0x00060050,0x00000012,0x0000003e,0x0000003d,0x0000003d,0x0000003d,
+  0x00050039,0x0000000f,0x0000003f,0x00000005,0x00000038,0x000600a9,0x0000000f,0x00000040,
+  0x0000003e,0x0000003f,0x00000038,0x00040053,0x0000000f,0x00000041,0x00000036,0x0005008e,
+  0x0000000f,0x00000042,0x00000041,0x00000037,0x00060050,0x00000012,0x00000043,0x00000009,
+  0x00000009,0x00000009,0x000600a9,0x0000000f,0x00000044,0x00000043,0x00000042,0x00000041,
+  0x00050085,0x0000000f,0x00000045,0x00000044,0x00000040,0x00050085,0x0000000c,0x00000046,
+  0x00000037,0x00000039,0x00040053,0x0000000f,0x00000047,0x00000045,0x00050050,0x00000010,
+  0x00000048,0x00000047,0x00000046,0x0003003e,0x00000003,0x00000048,0x000100fd,0x00010038,
+  0x00050036,0x0000000f,0x00000005,0x00000000,0x00000013,0x00030037,0x0000000f,0x00000049,
+  0x000200f8,0x0000004a,0x00050085,0x0000000f,0x0000004b,0x00000049,0x00000025,0x0007000c,
+  0x0000000f,0x0000004c,0x00000001,0x0000001a,0x00000049,0x0000002a,0x00050085,0x0000000f,
+  0x0000004d,0x00000027,0x0000004c,0x00050083,0x0000000f,0x0000004e,0x0000004d,0x00000026,
+  0x000500ba,0x00000012,0x0000004f,0x00000049,0x0000002b,0x000600a9,0x0000000f,0x00000050,
+  0x0000004f,0x0000004e,0x0000004b,0x000200fe,0x00000050,0x00010038,
 ];
 
-/// The ImGUI context.
-pub struct HalaImGui {
-  #[allow(dead_code)]
-  vert_shader: hala_gfx::HalaShader,
-  #[allow(dead_code)]
-  frag_shader: hala_gfx::HalaShader,
+// # glslangValidator -V -x -o glsl_shader_linear.frag.u32 glsl_shader_linear.frag
+/*
+#version 450 core
+layout(location = 0) out vec4 fColor;
+layout(set=0, binding=0) uniform sampler2D sTexture;
+layout(push_constant) uniform uPushConstant { layout(offset = 16) float uPaperWhite; layout(offset = 24) uint uFlags; } pc;
+layout(constant_id = 0) const bool PREMULTIPLY_COLOR = false;
+layout(location = 0) in struct { vec4 Color; vec2 UV; } In;
+vec3 srgb_to_linear(vec3 c)
+{
+    return mix(c / 12.92, pow((c + 0.055) / 1.055, vec3(2.4)), greaterThan(c, vec3(0.04045)));
+}
+void main()
+{
+    vec4 tex = texture(sTexture, In.UV.st);
+    // Bit 0 of uFlags: the texture is sampled as linear colors, e.g. the float and the sRGB formats.
+    vec3 tex_rgb = (pc.uFlags & 1u) != 0u ? tex.rgb : srgb_to_linear(tex.rgb);
+    vec3 color = srgb_to_linear(In.Color.rgb);
+    // The premultiplied alpha pipeline premultiplies the straight alpha vertex colors.
+    if (PREMULTIPLY_COLOR) color *= In.Color.a;
+    fColor = vec4(color * tex_rgb, In.Color.a * tex.a);
+}
+*/
+const __GLSL_SHADER_FRAG_LINEAR_SPV: [u32; 479] = [
+  0x07230203,0x00010000,0x00070000,0x00000051,0x00000000,0x00020011,0x00000001,0x0006000b,
+  0x00000001,0x4c534c47,0x6474732e,0x3035342e,0x00000000,0x0003000e,0x00000000,0x00000001,
+  0x0007000f,0x00000004,0x00000002,0x6e69616d,0x00000000,0x00000003,0x00000004,0x00030010,
+  0x00000002,0x00000007,0x00030003,0x00000002,0x000001c2,0x00040005,0x00000002,0x6e69616d,
+  0x00000000,0x00070005,0x00000005,0x62677273,0x5f6f745f,0x656e696c,0x76287261,0x003b3366,
+  0x00040005,0x00000003,0x6c6f4366,0x0000726f,0x00050005,0x00000006,0x78655473,0x65727574,
+  0x00000000,0x00030005,0x00000004,0x00006e49,0x00060005,0x00000007,0x73755075,0x6e6f4368,
+  0x6e617473,0x00000074,0x00060006,0x00000007,0x00000000,0x70615075,0x68577265,0x00657469,
+  0x00050006,0x00000007,0x00000001,0x616c4675,0x00007367,0x00030005,0x00000008,0x00006370,
+  0x00070005,0x00000009,0x4d455250,0x49544c55,0x5f594c50,0x4f4c4f43,0x00000052,0x00040047,
+  0x00000003,0x0000001e,0x00000000,0x00040047,0x00000006,0x00000022,0x00000000,0x00040047,
+  0x00000006,0x00000021,0x00000000,0x00040047,0x00000004,0x0000001e,0x00000000,0x00050048,
+  0x00000007,0x00000000,0x00000023,0x00000010,0x00050048,0x00000007,0x00000001,0x00000023,
+  0x00000018,0x00030047,0x00000007,0x00000002,0x00040047,0x00000009,0x00000001,0x00000000,
+  0x00020013,0x0000000a,0x00030021,0x0000000b,0x0000000a,0x00030016,0x0000000c,0x00000020,
+  0x00040015,0x0000000d,0x00000020,0x00000000,0x00040017,0x0000000e,0x0000000c,0x00000002,
+  0x00040017,0x0000000f,0x0000000c,0x00000003,0x00040017,0x00000010,0x0000000c,0x00000004,
+  0x00020014,0x00000011,0x00040017,0x00000012,0x00000011,0x00000003,0x00040021,0x00000013,
+  0x0000000f,0x0000000f,0x00040015,0x00000014,0x00000020,0x00000001,0x0004002b,0x00000014,
+  0x00000015,0x00000000,0x0004002b,0x00000014,0x00000016,0x00000001,0x0004002b,0x00000014,
+  0x00000017,0x00000002,0x0004002b,0x0000000d,0x00000018,0x00000000,0x0004002b,0x0000000d,
+  0x00000019,0x00000001,0x00040020,0x0000001a,0x00000003,0x00000010,0x0004003b,0x0000001a,
+  0x00000003,0x00000003,0x00090019,0x0000001b,0x0000000c,0x00000001,0x00000000,0x00000000,
+  0x00000000,0x00000001,0x00000000,0x0003001b,0x0000001c,0x0000001b,0x00040020,0x0000001d,
+  0x00000000,0x0000001c,0x0004001e,0x0000001e,0x00000010,0x0000000e,0x00040020,0x0000001f,
+  0x00000001,0x0000001e,0x0004003b,0x0000001f,0x00000004,0x00000001,0x00040020,0x00000020,
+  0x00000001,0x00000010,0x00040020,0x00000021,0x00000001,0x0000000e,0x0004002b,0x0000000c,
+  0x00000022,0x414eb852,0x0004002b,0x0000000c,0x00000023,0x3d6147ae,0x0004002b,0x0000000c,
+  0x00000024,0x3f870a3d,0x0006002c,0x0000000f,0x00000025,0x00000022,0x00000022,0x00000022,
+  0x0006002c,0x0000000f,0x00000026,0x00000023,0x00000023,0x00000023,0x0006002c,0x0000000f,
+  0x00000027,0x00000024,0x00000024,0x00000024,0x0004002b,0x0000000c,0x00000028,0x4019999a,
+  0x0004002b,0x0000000c,0x00000029,0x3d25aee6,0x0006002c,0x0000000f,0x0000002a,0x00000028,
+  0x00000028,0x00000028,0x0006002c,0x0000000f,0x0000002b,0x00000029,0x00000029,0x00000029,
+  0x00030031,0x00000011,0x00000009,0x0004003b,0x0000001d,0x00000006,0x00000000,0x0004001e,
+  0x00000007,0x0000000c,0x0000000d,0x00040020,0x0000002c,0x00000009,0x00000007,0x0004003b,
+  0x0000002c,0x00000008,0x00000009,0x00040020,0x0000002d,0x00000009,0x0000000c,0x00040020,
+  0x0000002e,0x00000009,0x0000000d,0x00050036,0x0000000a,0x00000002,0x00000000,0x0000000b,
+  0x000200f8,0x0000002f,0x00050041,0x00000021,0x00000030,0x00000004,0x00000016,0x0004003d,
+  0x0000000e,0x00000031,0x00000030,0x0004003d,0x0000001c,0x00000032,0x00000006,0x00050057,
+  0x00000010,0x00000033,0x00000032,0x00000031,0x00050041,0x00000020,0x00000034,0x00000004,
+  0x00000015,0x0004003d,0x00000010,0x00000035,0x00000034,0x0008004f,0x0000000f,0x00000036,
+  0x00000035,0x00000035,0x00000000,0x00000001,0x00000002,0x00050051,0x0000000c,0x00000037,
+  0x00000035,0x00000003,0x0008004f,0x0000000f,0x00000038,0x00000033,0x00000033,0x00000000,
+  0x00000001,0x00000002,0x00050051,0x0000000c,0x00000039,0x00000033,0x00000003,0x00050041,
+  0x0000002e,0x0000003a,0x00000008,0x00000016,0x0004003d,0x0000000d,0x0000003b,0x0000003a,
+  0x000500c7,0x0000000d,0x0000003c,0x0000003b,0x00000019,0x000500ab,0x00000011,0x0000003d,
+  0x0000003c,0x00000018,0x00060050,0x00000012,0x0000003e,0x0000003d,0x0000003d,0x0000003d,
+  0x00050039,0x0000000f,0x0000003f,0x00000005,0x00000036,0x00050039,0x0000000f,0x00000040,
+  0x00000005,0x00000038,0x000600a9,0x0000000f,0x00000041,0x0000003e,0x00000038,0x00000040,
+  0x0005008e,0x0000000f,0x00000042,0x0000003f,0x00000037,0x00060050,0x00000012,0x00000043,
+  0x00000009,0x00000009,0x00000009,0x000600a9,0x0000000f,0x00000044,0x00000043,0x00000042,
+  0x0000003f,0x00050085,0x0000000f,0x00000045,0x00000044,0x00000041,0x00050085,0x0000000c,
+  0x00000046,0x00000037,0x00000039,0x00040053,0x0000000f,0x00000047,0x00000045,0x00050050,
+  0x00000010,0x00000048,0x00000047,0x00000046,0x0003003e,0x00000003,0x00000048,0x000100fd,
+  0x00010038,0x00050036,0x0000000f,0x00000005,0x00000000,0x00000013,0x00030037,0x0000000f,
+  0x00000049,0x000200f8,0x0000004a,0x00050088,0x0000000f,0x0000004b,0x00000049,0x00000025,
+  0x00050081,0x0000000f,0x0000004c,0x00000049,0x00000026,0x00050088,0x0000000f,0x0000004d,
+  0x0000004c,0x00000027,0x0007000c,0x0000000f,0x0000004e,0x00000001,0x0000001a,0x0000004d,
+  0x0000002a,0x000500ba,0x00000012,0x0000004f,0x00000049,0x0000002b,0x000600a9,0x0000000f,
+  0x00000050,0x0000004f,0x0000004e,0x0000004b,0x000200fe,0x00000050,0x00010038,
+];
+
+// # glslangValidator -V -x -o glsl_shader_scrgb.frag.u32 glsl_shader_scrgb.frag
+/*
+#version 450 core
+layout(location = 0) out vec4 fColor;
+layout(set=0, binding=0) uniform sampler2D sTexture;
+layout(push_constant) uniform uPushConstant { layout(offset = 16) float uPaperWhite; layout(offset = 24) uint uFlags; } pc;
+layout(constant_id = 0) const bool PREMULTIPLY_COLOR = false;
+layout(location = 0) in struct { vec4 Color; vec2 UV; } In;
+vec3 srgb_to_linear(vec3 c)
+{
+    return mix(c / 12.92, pow((c + 0.055) / 1.055, vec3(2.4)), greaterThan(c, vec3(0.04045)));
+}
+void main()
+{
+    vec4 tex = texture(sTexture, In.UV.st);
+    // Bit 0 of uFlags: the texture is sampled as linear colors, e.g. the float and the sRGB formats.
+    vec3 tex_rgb = (pc.uFlags & 1u) != 0u ? tex.rgb : srgb_to_linear(tex.rgb);
+    vec3 color = srgb_to_linear(In.Color.rgb);
+    // The premultiplied alpha pipeline premultiplies the straight alpha vertex colors.
+    if (PREMULTIPLY_COLOR) color *= In.Color.a;
+    // 1.0 is 80 nits in scRGB.
+    fColor = vec4(color * tex_rgb * (pc.uPaperWhite / 80.0), In.Color.a * tex.a);
+}
+*/
+const __GLSL_SHADER_FRAG_SCRGB_SPV: [u32; 498] = [
+  0x07230203,0x00010000,0x00070000,0x00000055,0x00000000,0x00020011,0x00000001,0x0006000b,
+  0x00000001,0x4c534c47,0x6474732e,0x3035342e,0x00000000,0x0003000e,0x00000000,0x00000001,
+  0x0007000f,0x00000004,0x00000002,0x6e69616d,0x00000000,0x00000003,0x00000004,0x00030010,
+  0x00000002,0x00000007,0x00030003,0x00000002,0x000001c2,0x00040005,0x00000002,0x6e69616d,
+  0x00000000,0x00070005,0x00000005,0x62677273,0x5f6f745f,0x656e696c,0x76287261,0x003b3366,
+  0x00040005,0x00000003,0x6c6f4366,0x0000726f,0x00050005,0x00000006,0x78655473,0x65727574,
+  0x00000000,0x00030005,0x00000004,0x00006e49,0x00060005,0x00000007,0x73755075,0x6e6f4368,
+  0x6e617473,0x00000074,0x00060006,0x00000007,0x00000000,0x70615075,0x68577265,0x00657469,
+  0x00050006,0x00000007,0x00000001,0x616c4675,0x00007367,0x00030005,0x00000008,0x00006370,
+  0x00070005,0x00000009,0x4d455250,0x49544c55,0x5f594c50,0x4f4c4f43,0x00000052,0x00040047,
+  0x00000003,0x0000001e,0x00000000,0x00040047,0x00000006,0x00000022,0x00000000,0x00040047,
+  0x00000006,0x00000021,0x00000000,0x00040047,0x00000004,0x0000001e,0x00000000,0x00050048,
+  0x00000007,0x00000000,0x00000023,0x00000010,0x00050048,0x00000007,0x00000001,0x00000023,
+  0x00000018,0x00030047,0x00000007,0x00000002,0x00040047,0x00000009,0x00000001,0x00000000,
+  0x00020013,0x0000000a,0x00030021,0x0000000b,0x0000000a,0x00030016,0x0000000c,0x00000020,
+  0x00040015,0x0000000d,0x00000020,0x00000000,0x00040017,0x0000000e,0x0000000c,0x00000002,
+  0x00040017,0x0000000f,0x0000000c,0x00000003,0x00040017,0x00000010,0x0000000c,0x00000004,
+  0x00020014,0x00000011,0x00040017,0x00000012,0x00000011,0x00000003,0x00040021,0x00000013,
+  0x0000000f,0x0000000f,0x00040015,0x00000014,0x00000020,0x00000001,0x0004002b,0x00000014,
+  0x00000015,0x00000000,0x0004002b,0x00000014,0x00000016,0x00000001,0x0004002b,0x00000014,
+  0x00000017,0x00000002,0x0004002b,0x0000000d,0x00000018,0x00000000,0x0004002b,0x0000000d,
+  0x00000019,0x00000001,0x00040020,0x0000001a,0x00000003,0x00000010,0x0004003b,0x0000001a,
+  0x00000003,0x00000003,0x00090019,0x0000001b,0x0000000c,0x00000001,0x00000000,0x00000000,
+  0x00000000,0x00000001,0x00000000,0x0003001b,0x0000001c,0x0000001b,0x00040020,0x0000001d,
+  0x00000000,0x0000001c,0x0004001e,0x0000001e,0x00000010,0x0000000e,0x00040020,0x0000001f,
+  0x00000001,0x0000001e,0x0004003b,0x0000001f,0x00000004,0x00000001,0x00040020,0x00000020,
+  0x00000001,0x00000010,0x00040020,0x00000021,0x00000001,0x0000000e,0x0004002b,0x0000000c,
+  0x00000022,0x414eb852,0x0004002b,0x0000000c,0x00000023,0x3d6147ae,0x0004002b,0x0000000c,
+  0x00000024,0x3f870a3d,0x0006002c,0x0000000f,0x00000025,0x00000022,0x00000022,0x00000022,
+  0x0006002c,0x0000000f,0x00000026,0x00000023,0x00000023,0x00000023,0x0006002c,0x0000000f,
+  0x00000027,0x00000024,0x00000024,0x00000024,0x0004002b,0x0000000c,0x00000028,0x4019999a,
+  0x0004002b,0x0000000c,0x00000029,0x3d25aee6,0x0006002c,0x0000000f,0x0000002a,0x00000028,
+  0x00000028,0x00000028,0x0006002c,0x0000000f,0x0000002b,0x00000029,0x00000029,0x00000029,
+  0x00030031,0x00000011,0x00000009,0x0004003b,0x0000001d,0x00000006,0x00000000,0x0004001e,
+  0x00000007,0x0000000c,0x0000000d,0x00040020,0x0000002c,0x00000009,0x00000007,0x0004003b,
+  0x0000002c,0x00000008,0x00000009,0x00040020,0x0000002d,0x00000009,0x0000000c,0x00040020,
+  0x0000002e,0x00000009,0x0000000d,0x0004002b,0x0000000c,0x0000002f,0x42a00000,0x00050036,
+  0x0000000a,0x00000002,0x00000000,0x0000000b,0x000200f8,0x00000030,0x00050041,0x00000021,
+  0x00000031,0x00000004,0x00000016,0x0004003d,0x0000000e,0x00000032,0x00000031,0x0004003d,
+  0x0000001c,0x00000033,0x00000006,0x00050057,0x00000010,0x00000034,0x00000033,0x00000032,
+  0x00050041,0x00000020,0x00000035,0x00000004,0x00000015,0x0004003d,0x00000010,0x00000036,
+  0x00000035,0x0008004f,0x0000000f,0x00000037,0x00000036,0x00000036,0x00000000,0x00000001,
+  0x00000002,0x00050051,0x0000000c,0x00000038,0x00000036,0x00000003,0x0008004f,0x0000000f,
+  0x00000039,0x00000034,0x00000034,0x00000000,0x00000001,0x00000002,0x00050051,0x0000000c,
+  0x0000003a,0x00000034,0x00000003,0x00050041,0x0000002e,0x0000003b,0x00000008,0x00000016,
+  0x0004003d,0x0000000d,0x0000003c,0x0000003b,0x000500c7,0x0000000d,0x0000003d,0x0000003c,
+  0x00000019,0x000500ab,0x00000011,0x0000003e,0x0000003d,0x00000018,0x00060050,0x00000012,
+  0x0000003f,0x0000003e,0x0000003e,0x0000003e,0x00050039,0x0000000f,0x00000040,0x00000005,
+  0x00000037,0x00050039,0x0000000f,0x00000041,0x00000005,0x00000039,0x000600a9,0x0000000f,
+  0x00000042,0x0000003f,0x00000039,0x00000041,0x0005008e,0x0000000f,0x00000043,0x00000040,
+  0x00000038,0x00060050,0x00000012,0x00000044,0x00000009,0x00000009,0x00000009,0x000600a9,
+  0x0000000f,0x00000045,0x00000044,0x00000043,0x00000040,0x00050085,0x0000000f,0x00000046,
+  0x00000045,0x00000042,0x00050085,0x0000000c,0x00000047,0x00000038,0x0000003a,0x00050041,
+  0x0000002d,0x00000048,0x00000008,0x00000015,0x0004003d,0x0000000c,0x00000049,0x00000048,
+  0x00050088,0x0000000c,0x0000004a,0x00000049,0x0000002f,0x0005008e,0x0000000f,0x0000004b,
+  0x00000046,0x0000004a,0x00050050,0x00000010,0x0000004c,0x0000004b,0x00000047,0x0003003e,
+  0x00000003,0x0000004c,0x000100fd,0x00010038,0x00050036,0x0000000f,0x00000005,0x00000000,
+  0x00000013,0x00030037,0x0000000f,0x0000004d,0x000200f8,0x0000004e,0x00050088,0x0000000f,
+  0x0000004f,0x0000004d,0x00000025,0x00050081,0x0000000f,0x00000050,0x0000004d,0x00000026,
+  0x00050088,0x0000000f,0x00000051,0x00000050,0x00000027,0x0007000c,0x0000000f,0x00000052,
+  0x00000001,0x0000001a,0x00000051,0x0000002a,0x000500ba,0x00000012,0x00000053,0x0000004d,
+  0x0000002b,0x000600a9,0x0000000f,0x00000054,0x00000053,0x00000052,0x0000004f,0x000200fe,
+  0x00000054,0x00010038,
+];
+
+// # glslangValidator -V -x -o glsl_shader_hdr10.frag.u32 glsl_shader_hdr10.frag
+/*
+#version 450 core
+layout(location = 0) out vec4 fColor;
+layout(set=0, binding=0) uniform sampler2D sTexture;
+layout(push_constant) uniform uPushConstant { layout(offset = 16) float uPaperWhite; layout(offset = 24) uint uFlags; } pc;
+layout(constant_id = 0) const bool PREMULTIPLY_COLOR = false;
+layout(location = 0) in struct { vec4 Color; vec2 UV; } In;
+const mat3 BT709_TO_BT2020 = mat3(
+    0.6274040, 0.0690970, 0.0163916,
+    0.3292820, 0.9195400, 0.0880132,
+    0.0433136, 0.0113612, 0.8955950);
+vec3 srgb_to_linear(vec3 c)
+{
+    return mix(c / 12.92, pow((c + 0.055) / 1.055, vec3(2.4)), greaterThan(c, vec3(0.04045)));
+}
+void main()
+{
+    vec4 tex = texture(sTexture, In.UV.st);
+    // Bit 0 of uFlags: the texture is sampled as linear colors, e.g. the float and the sRGB formats.
+    vec3 tex_rgb = (pc.uFlags & 1u) != 0u ? tex.rgb : srgb_to_linear(tex.rgb);
+    vec3 color = srgb_to_linear(In.Color.rgb);
+    // The premultiplied alpha pipeline premultiplies the straight alpha vertex colors.
+    if (PREMULTIPLY_COLOR) color *= In.Color.a;
+    // SMPTE ST 2084(PQ) of the BT.2020 colors, 1.0 is 10000 nits.
+    vec3 y = clamp(BT709_TO_BT2020 * (color * tex_rgb) * (pc.uPaperWhite / 10000.0), 0.0, 1.0);
+    vec3 ym1 = pow(y, vec3(0.1593017578125));
+    vec3 pq = pow((0.8359375 + 18.8515625 * ym1) / (1.0 + 18.6875 * ym1), vec3(78.84375));
+    fColor = vec4(pq, In.Color.a * tex.a);
+}
+*/
+const __GLSL_SHADER_FRAG_HDR10_SPV: [u32; 684] = [
+  0x07230203,0x00010000,0x00070000,0x0000007a,0x00000000,0x00020011,0x00000001,0x0006000b,
+  0x00000001,0x4c534c47,0x6474732e,0x3035342e,0x00000000,0x0003000e,0x00000000,0x00000001,
+  0x0007000f,0x00000004,0x00000002,0x6e69616d,0x00000000,0x00000003,0x00000004,0x00030010,
+  0x00000002,0x00000007,0x00030003,0x00000002,0x000001c2,0x00040005,0x00000002,0x6e69616d,
+  0x00000000,0x00070005,0x00000005,0x62677273,0x5f6f745f,0x656e696c,0x76287261,0x003b3366,
+  0x00040005,0x00000003,0x6c6f4366,0x0000726f,0x00050005,0x00000006,0x78655473,0x65727574,
+  0x00000000,0x00030005,0x00000004,0x00006e49,0x00060005,0x00000007,0x73755075,0x6e6f4368,
+  0x6e617473,0x00000074,0x00060006,0x00000007,0x00000000,0x70615075,0x68577265,0x00657469,
+  0x00050006,0x00000007,0x00000001,0x616c4675,0x00007367,0x00030005,0x00000008,0x00006370,
+  0x00070005,0x00000009,0x4d455250,0x49544c55,0x5f594c50,0x4f4c4f43,0x00000052,0x00040047,
+  0x00000003,0x0000001e,0x00000000,0x00040047,0x00000006,0x00000022,0x00000000,0x00040047,
+  0x00000006,0x00000021,0x00000000,0x00040047,0x00000004,0x0000001e,0x00000000,0x00050048,
+  0x00000007,0x00000000,0x00000023,0x00000010,0x00050048,0x00000007,0x00000001,0x00000023,
+  0x00000018,0x00030047,0x00000007,0x00000002,0x00040047,0x00000009,0x00000001,0x00000000,
+  0x00020013,0x0000000a,0x00030021,0x0000000b,0x0000000a,0x00030016,0x0000000c,0x00000020,
+  0x00040015,0x0000000d,0x00000020,0x00000000,0x00040017,0x0000000e,0x0000000c,0x00000002,
+  0x00040017,0x0000000f,0x0000000c,0x00000003,0x00040017,0x00000010,0x0000000c,0x00000004,
+  0x00020014,0x00000011,0x00040017,0x00000012,0x00000011,0x00000003,0x00040021,0x00000013,
+  0x0000000f,0x0000000f,0x00040015,0x00000014,0x00000020,0x00000001,0x0004002b,0x00000014,
+  0x00000015,0x00000000,0x0004002b,0x00000014,0x00000016,0x00000001,0x0004002b,0x00000014,
+  0x00000017,0x00000002,0x0004002b,0x0000000d,0x00000018,0x00000000,0x0004002b,0x0000000d,
+  0x00000019,0x00000001,0x00040020,0x0000001a,0x00000003,0x00000010,0x0004003b,0x0000001a,
+  0x00000003,0x00000003,0x00090019,0x0000001b,0x0000000c,0x00000001,0x00000000,0x00000000,
+  0x00000000,0x00000001,0x00000000,0x0003001b,0x0000001c,0x0000001b,0x00040020,0x0000001d,
+  0x00000000,0x0000001c,0x0004001e,0x0000001e,0x00000010,0x0000000e,0x00040020,0x0000001f,
+  0x00000001,0x0000001e,0x0004003b,0x0000001f,0x00000004,0x00000001,0x00040020,0x00000020,
+  0x00000001,0x00000010,0x00040020,0x00000021,0x00000001,0x0000000e,0x0004002b,0x0000000c,
+  0x00000022,0x414eb852,0x0004002b,0x0000000c,0x00000023,0x3d6147ae,0x0004002b,0x0000000c,
+  0x00000024,0x3f870a3d,0x0006002c,0x0000000f,0x00000025,0x00000022,0x00000022,0x00000022,
+  0x0006002c,0x0000000f,0x00000026,0x00000023,0x00000023,0x00000023,0x0006002c,0x0000000f,
+  0x00000027,0x00000024,0x00000024,0x00000024,0x0004002b,0x0000000c,0x00000028,0x4019999a,
+  0x0004002b,0x0000000c,0x00000029,0x3d25aee6,0x0006002c,0x0000000f,0x0000002a,0x00000028,
+  0x00000028,0x00000028,0x0006002c,0x0000000f,0x0000002b,0x00000029,0x00000029,0x00000029,
+  0x00030031,0x00000011,0x00000009,0x0004003b,0x0000001d,0x00000006,0x00000000,0x0004001e,
+  0x00000007,0x0000000c,0x0000000d,0x00040020,0x0000002c,0x00000009,0x00000007,0x0004003b,
+  0x0000002c,0x00000008,0x00000009,0x00040020,0x0000002d,0x00000009,0x0000000c,0x00040020,
+  0x0000002e,0x00000009,0x0000000d,0x00040018,0x0000002f,0x0000000f,0x00000003,0x0004002b,
+  0x0000000c,0x00000030,0x3f209d8c,0x0004002b,0x0000000c,0x00000031,0x3d8d82ba,0x0004002b,
+  0x0000000c,0x00000032,0x3c8647ad,0x0004002b,0x0000000c,0x00000033,0x3ea897a6,0x0004002b,
+  0x0000000c,0x00000034,0x3f6b66f9,0x0004002b,0x0000000c,0x00000035,0x3db44044,0x0004002b,
+  0x0000000c,0x00000036,0x3d31699a,0x0004002b,0x0000000c,0x00000037,0x3c3a2454,0x0004002b,
+  0x0000000c,0x00000038,0x3f6545b7,0x0006002c,0x0000000f,0x00000039,0x00000030,0x00000031,
+  0x00000032,0x0006002c,0x0000000f,0x0000003a,0x00000033,0x00000034,0x00000035,0x0006002c,
+  0x0000000f,0x0000003b,0x00000036,0x00000037,0x00000038,0x0006002c,0x0000002f,0x0000003c,
+  0x00000039,0x0000003a,0x0000003b,0x0004002b,0x0000000c,0x0000003d,0x461c4000,0x0004002b,
+  0x0000000c,0x0000003e,0x00000000,0x0004002b,0x0000000c,0x0000003f,0x3f800000,0x0006002c,
+  0x0000000f,0x00000040,0x0000003e,0x0000003e,0x0000003e,0x0006002c,0x0000000f,0x00000041,
+  0x0000003f,0x0000003f,0x0000003f,0x0004002b,0x0000000c,0x00000042,0x3e232000,0x0004002b,
+  0x0000000c,0x00000043,0x429db000,0x0004002b,0x0000000c,0x00000044,0x3f560000,0x0004002b,
+  0x0000000c,0x00000045,0x4196d000,0x0004002b,0x0000000c,0x00000046,0x41958000,0x0006002c,
+  0x0000000f,0x00000047,0x00000042,0x00000042,0x00000042,0x0006002c,0x0000000f,0x00000048,
+  0x00000043,0x00000043,0x00000043,0x0006002c,0x0000000f,0x00000049,0x00000044,0x00000044,
+  0x00000044,0x0006002c,0x0000000f,0x0000004a,0x00000045,0x00000045,0x00000045,0x0006002c,
+  0x0000000f,0x0000004b,0x00000046,0x00000046,0x00000046,0x00050036,0x0000000a,0x00000002,
+  0x00000000,0x0000000b,0x000200f8,0x0000004c,0x00050041,0x00000021,0x0000004d,0x00000004,
+  0x00000016,0x0004003d,0x0000000e,0x0000004e,0x0000004d,0x0004003d,0x0000001c,0x0000004f,
+  0x00000006,0x00050057,0x00000010,0x00000050,0x0000004f,0x0000004e,0x00050041,0x00000020,
+  0x00000051,0x00000004,0x00000015,0x0004003d,0x00000010,0x00000052,0x00000051,0x0008004f,
+  0x0000000f,0x00000053,0x00000052,0x00000052,0x00000000,0x00000001,0x00000002,0x00050051,
+  0x0000000c,0x00000054,0x00000052,0x00000003,0x0008004f,0x0000000f,0x00000055,0x00000050,
+  0x00000050,0x00000000,0x00000001,0x00000002,0x00050051,0x0000000c,0x00000056,0x00000050,
+  0x00000003,0x00050041,0x0000002e,0x00000057,0x00000008,0x00000016,0x0004003d,0x0000000d,
+  0x00000058,0x00000057,0x000500c7,0x0000000d,0x00000059,0x00000058,0x00000019,0x000500ab,
+  0x00000011,0x0000005a,0x00000059,0x00000018,0x00060050,0x00000012,0x0000005b,0x0000005a,
+  0x0000005a,0x0000005a,0x00050039,0x0000000f,0x0000005c,0x00000005,0x00000053,0x00050039,
+  0x0000000f,0x0000005d,0x00000005,0x00000055,0x000600a9,0x0000000f,0x0000005e,0x0000005b,
+  0x00000055,0x0000005d,0x0005008e,0x0000000f,0x0000005f,0x0000005c,0x00000054,0x00060050,
+  0x00000012,0x00000060,0x00000009,0x00000009,0x00000009,0x000600a9,0x0000000f,0x00000061,
+  0x00000060,0x0000005f,0x0000005c,0x00050085,0x0000000f,0x00000062,0x00000061,0x0000005e,
+  0x00050085,0x0000000c,0x00000063,0x00000054,0x00000056,0x00050041,0x0000002d,0x00000064,
+  0x00000008,0x00000015,0x0004003d,0x0000000c,0x00000065,0x00000064,0x00050091,0x0000000f,
+  0x00000066,0x0000003c,0x00000062,0x00050088,0x0000000c,0x00000067,0x00000065,0x0000003d,
+  0x0005008e,0x0000000f,0x00000068,0x00000066,0x00000067,0x0008000c,0x0000000f,0x00000069,
+  0x00000001,0x0000002b,0x00000068,0x00000040,0x00000041,0x0007000c,0x0000000f,0x0000006a,
+  0x00000001,0x0000001a,0x00000069,0x00000047,0x00050085,0x0000000f,0x0000006b,0x0000004a,
+  0x0000006a,0x00050081,0x0000000f,0x0000006c,0x00000049,0x0000006b,0x00050085,0x0000000f,
+  0x0000006d,0x0000004b,0x0000006a,0x00050081,0x0000000f,0x0000006e,0x00000041,0x0000006d,
+  0x00050088,0x0000000f,0x0000006f,0x0000006c,0x0000006e,0x0007000c,0x0000000f,0x00000070,
+  0x00000001,0x0000001a,0x0000006f,0x00000048,0x00050050,0x00000010,0x00000071,0x00000070,
+  0x00000063,0x0003003e,0x00000003,0x00000071,0x000100fd,0x00010038,0x00050036,0x0000000f,
+  0x00000005,0x00000000,0x00000013,0x00030037,0x0000000f,0x00000072,0x000200f8,0x00000073,
+  0x00050088,0x0000000f,0x00000074,0x00000072,0x00000025,0x00050081,0x0000000f,0x00000075,
+  0x00000072,0x00000026,0x00050088,0x0000000f,0x00000076,0x00000075,0x00000027,0x0007000c,
+  0x0000000f,0x00000077,0x00000001,0x0000001a,0x00000076,0x0000002a,0x000500ba,0x00000012,
+  0x00000078,0x00000072,0x0000002b,0x000600a9,0x0000000f,0x00000079,0x00000078,0x00000077,
+  0x00000074,0x000200fe,0x00000079,0x00010038,
+];
+
+// # glslangValidator -V -x -o glsl_shader_bindless.frag.u32 glsl_shader_bindless.frag
+/*
+#version 450 core
+#extension GL_EXT_nonuniform_qualifier : require
+layout(location = 0) out vec4 fColor;
+layout(set=0, binding=0) uniform sampler2D sTextures[];
+layout(push_constant) uniform uPushConstant { layout(offset = 16) float uPaperWhite; uint uTextureIndex; uint uFlags; } pc;
+layout(constant_id = 0) const bool PREMULTIPLY_COLOR = false;
+layout(location = 0) in struct { vec4 Color; vec2 UV; } In;
+vec3 linear_to_srgb(vec3 c)
+{
+    return mix(c * 12.92, 1.055 * pow(c, vec3(1.0 / 2.4)) - 0.055, greaterThan(c, vec3(0.0031308)));
+}
+void main()
+{
+    vec4 tex = texture(sTextures[pc.uTextureIndex], In.UV.st);
+    // Bit 0 of uFlags: the texture is sampled as linear colors, e.g. the float and the sRGB formats.
+    vec3 tex_rgb = (pc.uFlags & 1u) != 0u ? linear_to_srgb(tex.rgb) : tex.rgb;
+    vec3 color = In.Color.rgb;
+    // The premultiplied alpha pipeline premultiplies the straight alpha vertex colors.
+    if (PREMULTIPLY_COLOR) color *= In.Color.a;
+    fColor = vec4(color * tex_rgb, In.Color.a * tex.a);
+}
+*/
+const __GLSL_SHADER_FRAG_BINDLESS_SPV: [u32; 524] = [
+  0x07230203,0x00010000,0x00070000,0x00000056,0x00000000,0x00020011,0x00000001,0x00020011,
+  0x0000001d,0x00020011,0x000014b6,0x0008000a,0x5f565053,0x5f545845,0x63736564,0x74706972,
+  0x695f726f,0x7865646e,0x00676e69,0x0006000b,0x00000001,0x4c534c47,0x6474732e,0x3035342e,
+  0x00000000,0x0003000e,0x00000000,0x00000001,0x0007000f,0x00000004,0x00000002,0x6e69616d,
+  0x00000000,0x00000003,0x00000004,0x00030010,0x00000002,0x00000007,0x00030003,0x00000002,
+  0x000001c2,0x00040005,0x00000002,0x6e69616d,0x00000000,0x00070005,0x00000005,0x656e696c,
+  0x745f7261,0x72735f6f,0x76286267,0x003b3366,0x00040005,0x00000003,0x6c6f4366,0x0000726f,
+  0x00050005,0x00000006,0x78655473,0x65727574,0x00000073,0x00030005,0x00000004,0x00006e49,
+  0x00060005,0x00000007,0x73755075,0x6e6f4368,0x6e617473,0x00000074,0x00060006,0x00000007,
+  0x00000000,0x70615075,0x68577265,0x00657469,0x00070006,0x00000007,0x00000001,0x78655475,
+  0x65727574,0x65646e49,0x00000078,0x00050006,0x00000007,0x00000002,0x616c4675,0x00007367,
+  0x00030005,0x00000008,0x00006370,0x00070005,0x00000009,0x4d455250,0x49544c55,0x5f594c50,
+  0x4f4c4f43,0x00000052,0x00040047,0x00000003,0x0000001e,0x00000000,0x00040047,0x00000006,
+  0x00000022,0x00000000,0x00040047,0x00000006,0x00000021,0x00000000,0x00040047,0x00000004,
+  0x0000001e,0x00000000,0x00050048,0x00000007,0x00000000,0x00000023,0x00000010,0x00050048,
+  0x00000007,0x00000001,0x00000023,0x00000014,0x00050048,0x00000007,0x00000002,0x00000023,
+  0x00000018,0x00030047,0x00000007,0x00000002,0x00040047,0x00000009,0x00000001,0x00000000,
+  0x00020013,0x0000000a,0x00030021,0x0000000b,0x0000000a,0x00030016,0x0000000c,0x00000020,
+  0x00040015,0x0000000d,0x00000020,0x00000000,0x00040017,0x0000000e,0x0000000c,0x00000002,
+  0x00040017,0x0000000f,0x0000000c,0x00000003,0x00040017,0x00000010,0x0000000c,0x00000004,
+  0x00020014,0x00000011,0x00040017,0x00000012,0x00000011,0x00000003,0x00040021,0x00000013,
+  0x0000000f,0x0000000f,0x00040015,0x00000014,0x00000020,0x00000001,0x0004002b,0x00000014,
+  0x00000015,0x00000000,0x0004002b,0x00000014,0x00000016,0x00000001,0x0004002b,0x00000014,
+  0x00000017,0x00000002,0x0004002b,0x0000000d,0x00000018,0x00000000,0x0004002b,0x0000000d,
+  0x00000019,0x00000001,0x00040020,0x0000001a,0x00000003,0x00000010,0x0004003b,0x0000001a,
+  0x00000003,0x00000003,0x00090019,0x0000001b,0x0000000c,0x00000001,0x00000000,0x00000000,
+  0x00000000,0x00000001,0x00000000,0x0003001b,0x0000001c,0x0000001b,0x00040020,0x0000001d,
+  0x00000000,0x0000001c,0x0004001e,0x0000001e,0x00000010,0x0000000e,0x00040020,0x0000001f,
+  0x00000001,0x0000001e,0x0004003b,0x0000001f,0x00000004,0x00000001,0x00040020,0x00000020,
+  0x00000001,0x00000010,0x00040020,0x00000021,0x00000001,0x0000000e,0x0004002b,0x0000000c,
+  0x00000022,0x414eb852,0x0004002b,0x0000000c,0x00000023,0x3d6147ae,0x0004002b,0x0000000c,
+  0x00000024,0x3f870a3d,0x0006002c,0x0000000f,0x00000025,0x00000022,0x00000022,0x00000022,
+  0x0006002c,0x0000000f,0x00000026,0x00000023,0x00000023,0x00000023,0x0006002c,0x0000000f,
+  0x00000027,0x00000024,0x00000024,0x00000024,0x0004002b,0x0000000c,0x00000028,0x3ed55555,
+  0x0004002b,0x0000000c,0x00000029,0x3b4d2e1c,0x0006002c,0x0000000f,0x0000002a,0x00000028,
+  0x00000028,0x00000028,0x0006002c,0x0000000f,0x0000002b,0x00000029,0x00000029,0x00000029,
+  0x00030031,0x00000011,0x00000009,0x0003001d,0x0000002c,0x0000001c,0x00040020,0x0000002d,
+  0x00000000,0x0000002c,0x0004003b,0x0000002d,0x00000006,0x00000000,0x0005001e,0x00000007,
+  0x0000000c,0x0000000d,0x0000000d,0x00040020,0x0000002e,0x00000009,0x00000007,0x0004003b,
+  0x0000002e,0x00000008,0x00000009,0x00040020,0x0000002f,0x00000009,0x0000000c,0x00040020,
+  0x00000030,0x00000009,0x0000000d,0x00050036,0x0000000a,0x00000002,0x00000000,0x0000000b,
+  0x000200f8,0x00000031,0x00050041,0x00000021,0x00000032,0x00000004,0x00000016,0x0004003d,
+  0x0000000e,0x00000033,0x00000032,0x00050041,0x00000030,0x00000034,0x00000008,0x00000016,
+  0x0004003d,0x0000000d,0x00000035,0x00000034,0x00050041,0x0000001d,0x00000036,0x00000006,
+  0x00000035,0x0004003d,0x0000001c,0x00000037,0x00000036,0x00050057,0x00000010,0x00000038,
+  0x00000037,0x00000033,0x00050041,0x00000020,0x00000039,0x00000004,0x00000015,0x0004003d,
+  0x00000010,0x0000003a,0x00000039,0x0008004f,0x0000000f,0x0000003b,0x0000003a,0x0000003a,
+  0x00000000,0x00000001,0x00000002,0x00050051,0x0000000c,0x0000003c,0x0000003a,0x00000003,
+  0x0008004f,0x0000000f,0x0000003d,0x00000038,0x00000038,0x00000000,0x00000001,0x00000002,
+  0x00050051,0x0000000c,0x0000003e,0x00000038,0x00000003,0x00050041,0x00000030,0x0000003f,
+  0x00000008,0x00000017,0x0004003d,0x0000000d,0x00000040,0x0000003f,0x000500c7,0x0000000d,
+  0x00000041,0x00000040,0x00000019,0x000500ab,0x00000011,0x00000042,0x00000041,0x00000018,
+  0x00060050,0x00000012,0x00000043,0x00000042,0x00000042,0x00000042,0x00050039,0x0000000f,
+  0x00000044,0x00000005,0x0000003d,0x000600a9,0x0000000f,0x00000045,0x00000043,0x00000044,
+  0x0000003d,0x00040053,0x0000000f,0x00000046,0x0000003b,0x0005008e,0x0000000f,0x00000047,
+  0x00000046,0x0000003c,0x00060050,0x00000012,0x00000048,0x00000009,0x00000009,0x00000009,
+  0x000600a9,0x0000000f,0x00000049,0x00000048,0x00000047,0x00000046,0x00050085,0x0000000f,
+  0x0000004a,0x00000049,0x00000045,0x00050085,0x0000000c,0x0000004b,0x0000003c,0x0000003e,
+  0x00040053,0x0000000f,0x0000004c,0x0000004a,0x00050050,0x00000010,0x0000004d,0x0000004c,
+  0x0000004b,0x0003003e,0x00000003,0x0000004d,0x000100fd,0x00010038,0x00050036,0x0000000f,
+  0x00000005,0x00000000,0x00000013,0x00030037,0x0000000f,0x0000004e,0x000200f8,0x0000004f,
+  0x00050085,0x0000000f,0x00000050,0x0000004e,0x00000025,0x0007000c,0x0000000f,0x00000051,
+  0x00000001,0x0000001a,0x0000004e,0x0000002a,0x00050085,0x0000000f,0x00000052,0x00000027,
+  0x00000051,0x00050083,0x0000000f,0x00000053,0x00000052,0x00000026,0x000500ba,0x00000012,
+  0x00000054,0x0000004e,0x0000002b,0x000600a9,0x0000000f,0x00000055,0x00000054,0x00000053,
+  0x00000050,0x000200fe,0x00000055,0x00010038,
+];
+
+// The bindless variants of glsl_shader_linear.frag, glsl_shader_scrgb.frag and glsl_shader_hdr10.frag.
+// They only replace sTexture with sTextures[pc.uTextureIndex] and the push constant block above.
+const __GLSL_SHADER_FRAG_BINDLESS_LINEAR_SPV: [u32; 525] = [
+  0x07230203,0x00010000,0x00070000,0x00000056,0x00000000,0x00020011,0x00000001,0x00020011,
+  0x0000001d,0x00020011,0x000014b6,0x0008000a,0x5f565053,0x5f545845,0x63736564,0x74706972,
+  0x695f726f,0x7865646e,0x00676e69,0x0006000b,0x00000001,0x4c534c47,0x6474732e,0x3035342e,
+  0x00000000,0x0003000e,0x00000000,0x00000001,0x0007000f,0x00000004,0x00000002,0x6e69616d,
+  0x00000000,0x00000003,0x00000004,0x00030010,0x00000002,0x00000007,0x00030003,0x00000002,
+  0x000001c2,0x00040005,0x00000002,0x6e69616d,0x00000000,0x00070005,0x00000005,0x62677273,
+  0x5f6f745f,0x656e696c,0x76287261,0x003b3366,0x00040005,0x00000003,0x6c6f4366,0x0000726f,
+  0x00050005,0x00000006,0x78655473,0x65727574,0x00000073,0x00030005,0x00000004,0x00006e49,
+  0x00060005,0x00000007,0x73755075,0x6e6f4368,0x6e617473,0x00000074,0x00060006,0x00000007,
+  0x00000000,0x70615075,0x68577265,0x00657469,0x00070006,0x00000007,0x00000001,0x78655475,
+  0x65727574,0x65646e49,0x00000078,0x00050006,0x00000007,0x00000002,0x616c4675,0x00007367,
+  0x00030005,0x00000008,0x00006370,0x00070005,0x00000009,0x4d455250,0x49544c55,0x5f594c50,
+  0x4f4c4f43,0x00000052,0x00040047,0x00000003,0x0000001e,0x00000000,0x00040047,0x00000006,
+  0x00000022,0x00000000,0x00040047,0x00000006,0x00000021,0x00000000,0x00040047,0x00000004,
+  0x0000001e,0x00000000,0x00050048,0x00000007,0x00000000,0x00000023,0x00000010,0x00050048,
+  0x00000007,0x00000001,0x00000023,0x00000014,0x00050048,0x00000007,0x00000002,0x00000023,
+  0x00000018,0x00030047,0x00000007,0x00000002,0x00040047,0x00000009,0x00000001,0x00000000,
+  0x00020013,0x0000000a,0x00030021,0x0000000b,0x0000000a,0x00030016,0x0000000c,0x00000020,
+  0x00040015,0x0000000d,0x00000020,0x00000000,0x00040017,0x0000000e,0x0000000c,0x00000002,
+  0x00040017,0x0000000f,0x0000000c,0x00000003,0x00040017,0x00000010,0x0000000c,0x00000004,
+  0x00020014,0x00000011,0x00040017,0x00000012,0x00000011,0x00000003,0x00040021,0x00000013,
+  0x0000000f,0x0000000f,0x00040015,0x00000014,0x00000020,0x00000001,0x0004002b,0x00000014,
+  0x00000015,0x00000000,0x0004002b,0x00000014,0x00000016,0x00000001,0x0004002b,0x00000014,
+  0x00000017,0x00000002,0x0004002b,0x0000000d,0x00000018,0x00000000,0x0004002b,0x0000000d,
+  0x00000019,0x00000001,0x00040020,0x0000001a,0x00000003,0x00000010,0x0004003b,0x0000001a,
+  0x00000003,0x00000003,0x00090019,0x0000001b,0x0000000c,0x00000001,0x00000000,0x00000000,
+  0x00000000,0x00000001,0x00000000,0x0003001b,0x0000001c,0x0000001b,0x00040020,0x0000001d,
+  0x00000000,0x0000001c,0x0004001e,0x0000001e,0x00000010,0x0000000e,0x00040020,0x0000001f,
+  0x00000001,0x0000001e,0x0004003b,0x0000001f,0x00000004,0x00000001,0x00040020,0x00000020,
+  0x00000001,0x00000010,0x00040020,0x00000021,0x00000001,0x0000000e,0x0004002b,0x0000000c,
+  0x00000022,0x414eb852,0x0004002b,0x0000000c,0x00000023,0x3d6147ae,0x0004002b,0x0000000c,
+  0x00000024,0x3f870a3d,0x0006002c,0x0000000f,0x00000025,0x00000022,0x00000022,0x00000022,
+  0x0006002c,0x0000000f,0x00000026,0x00000023,0x00000023,0x00000023,0x0006002c,0x0000000f,
+  0x00000027,0x00000024,0x00000024,0x00000024,0x0004002b,0x0000000c,0x00000028,0x4019999a,
+  0x0004002b,0x0000000c,0x00000029,0x3d25aee6,0x0006002c,0x0000000f,0x0000002a,0x00000028,
+  0x00000028,0x00000028,0x0006002c,0x0000000f,0x0000002b,0x00000029,0x00000029,0x00000029,
+  0x00030031,0x00000011,0x00000009,0x0003001d,0x0000002c,0x0000001c,0x00040020,0x0000002d,
+  0x00000000,0x0000002c,0x0004003b,0x0000002d,0x00000006,0x00000000,0x0005001e,0x00000007,
+  0x0000000c,0x0000000d,0x0000000d,0x00040020,0x0000002e,0x00000009,0x00000007,0x0004003b,
+  0x0000002e,0x00000008,0x00000009,0x00040020,0x0000002f,0x00000009,0x0000000c,0x00040020,
+  0x00000030,0x00000009,0x0000000d,0x00050036,0x0000000a,0x00000002,0x00000000,0x0000000b,
+  0x000200f8,0x00000031,0x00050041,0x00000021,0x00000032,0x00000004,0x00000016,0x0004003d,
+  0x0000000e,0x00000033,0x00000032,0x00050041,0x00000030,0x00000034,0x00000008,0x00000016,
+  0x0004003d,0x0000000d,0x00000035,0x00000034,0x00050041,0x0000001d,0x00000036,0x00000006,
+  0x00000035,0x0004003d,0x0000001c,0x00000037,0x00000036,0x00050057,0x00000010,0x00000038,
+  0x00000037,0x00000033,0x00050041,0x00000020,0x00000039,0x00000004,0x00000015,0x0004003d,
+  0x00000010,0x0000003a,0x00000039,0x0008004f,0x0000000f,0x0000003b,0x0000003a,0x0000003a,
+  0x00000000,0x00000001,0x00000002,0x00050051,0x0000000c,0x0000003c,0x0000003a,0x00000003,
+  0x0008004f,0x0000000f,0x0000003d,0x00000038,0x00000038,0x00000000,0x00000001,0x00000002,
+  0x00050051,0x0000000c,0x0000003e,0x00000038,0x00000003,0x00050041,0x00000030,0x0000003f,
+  0x00000008,0x00000017,0x0004003d,0x0000000d,0x00000040,0x0000003f,0x000500c7,0x0000000d,
+  0x00000041,0x00000040,0x00000019,0x000500ab,0x00000011,0x00000042,0x00000041,0x00000018,
+  0x00060050,0x00000012,0x00000043,0x00000042,0x00000042,0x00000042,0x00050039,0x0000000f,
+  0x00000044,0x00000005,0x0000003b,0x00050039,0x0000000f,0x00000045,0x00000005,0x0000003d,
+  0x000600a9,0x0000000f,0x00000046,0x00000043,0x0000003d,0x00000045,0x0005008e,0x0000000f,
+  0x00000047,0x00000044,0x0000003c,0x00060050,0x00000012,0x00000048,0x00000009,0x00000009,
+  0x00000009,0x000600a9,0x0000000f,0x00000049,0x00000048,0x00000047,0x00000044,0x00050085,
+  0x0000000f,0x0000004a,0x00000049,0x00000046,0x00050085,0x0000000c,0x0000004b,0x0000003c,
+  0x0000003e,0x00040053,0x0000000f,0x0000004c,0x0000004a,0x00050050,0x00000010,0x0000004d,
+  0x0000004c,0x0000004b,0x0003003e,0x00000003,0x0000004d,0x000100fd,0x00010038,0x00050036,
+  0x0000000f,0x00000005,0x00000000,0x00000013,0x00030037,0x0000000f,0x0000004e,0x000200f8,
+  0x0000004f,0x00050088,0x0000000f,0x00000050,0x0000004e,0x00000025,0x00050081,0x0000000f,
+  0x00000051,0x0000004e,0x00000026,0x00050088,0x0000000f,0x00000052,0x00000051,0x00000027,
+  0x0007000c,0x0000000f,0x00000053,0x00000001,0x0000001a,0x00000052,0x0000002a,0x000500ba,
+  0x00000012,0x00000054,0x0000004e,0x0000002b,0x000600a9,0x0000000f,0x00000055,0x00000054,
+  0x00000053,0x00000050,0x000200fe,0x00000055,0x00010038,
+];
+
+const __GLSL_SHADER_FRAG_BINDLESS_SCRGB_SPV: [u32; 544] = [
+  0x07230203,0x00010000,0x00070000,0x0000005a,0x00000000,0x00020011,0x00000001,0x00020011,
+  0x0000001d,0x00020011,0x000014b6,0x0008000a,0x5f565053,0x5f545845,0x63736564,0x74706972,
+  0x695f726f,0x7865646e,0x00676e69,0x0006000b,0x00000001,0x4c534c47,0x6474732e,0x3035342e,
+  0x00000000,0x0003000e,0x00000000,0x00000001,0x0007000f,0x00000004,0x00000002,0x6e69616d,
+  0x00000000,0x00000003,0x00000004,0x00030010,0x00000002,0x00000007,0x00030003,0x00000002,
+  0x000001c2,0x00040005,0x00000002,0x6e69616d,0x00000000,0x00070005,0x00000005,0x62677273,
+  0x5f6f745f,0x656e696c,0x76287261,0x003b3366,0x00040005,0x00000003,0x6c6f4366,0x0000726f,
+  0x00050005,0x00000006,0x78655473,0x65727574,0x00000073,0x00030005,0x00000004,0x00006e49,
+  0x00060005,0x00000007,0x73755075,0x6e6f4368,0x6e617473,0x00000074,0x00060006,0x00000007,
+  0x00000000,0x70615075,0x68577265,0x00657469,0x00070006,0x00000007,0x00000001,0x78655475,
+  0x65727574,0x65646e49,0x00000078,0x00050006,0x00000007,0x00000002,0x616c4675,0x00007367,
+  0x00030005,0x00000008,0x00006370,0x00070005,0x00000009,0x4d455250,0x49544c55,0x5f594c50,
+  0x4f4c4f43,0x00000052,0x00040047,0x00000003,0x0000001e,0x00000000,0x00040047,0x00000006,
+  0x00000022,0x00000000,0x00040047,0x00000006,0x00000021,0x00000000,0x00040047,0x00000004,
+  0x0000001e,0x00000000,0x00050048,0x00000007,0x00000000,0x00000023,0x00000010,0x00050048,
+  0x00000007,0x00000001,0x00000023,0x00000014,0x00050048,0x00000007,0x00000002,0x00000023,
+  0x00000018,0x00030047,0x00000007,0x00000002,0x00040047,0x00000009,0x00000001,0x00000000,
+  0x00020013,0x0000000a,0x00030021,0x0000000b,0x0000000a,0x00030016,0x0000000c,0x00000020,
+  0x00040015,0x0000000d,0x00000020,0x00000000,0x00040017,0x0000000e,0x0000000c,0x00000002,
+  0x00040017,0x0000000f,0x0000000c,0x00000003,0x00040017,0x00000010,0x0000000c,0x00000004,
+  0x00020014,0x00000011,0x00040017,0x00000012,0x00000011,0x00000003,0x00040021,0x00000013,
+  0x0000000f,0x0000000f,0x00040015,0x00000014,0x00000020,0x00000001,0x0004002b,0x00000014,
+  0x00000015,0x00000000,0x0004002b,0x00000014,0x00000016,0x00000001,0x0004002b,0x00000014,
+  0x00000017,0x00000002,0x0004002b,0x0000000d,0x00000018,0x00000000,0x0004002b,0x0000000d,
+  0x00000019,0x00000001,0x00040020,0x0000001a,0x00000003,0x00000010,0x0004003b,0x0000001a,
+  0x00000003,0x00000003,0x00090019,0x0000001b,0x0000000c,0x00000001,0x00000000,0x00000000,
+  0x00000000,0x00000001,0x00000000,0x0003001b,0x0000001c,0x0000001b,0x00040020,0x0000001d,
+  0x00000000,0x0000001c,0x0004001e,0x0000001e,0x00000010,0x0000000e,0x00040020,0x0000001f,
+  0x00000001,0x0000001e,0x0004003b,0x0000001f,0x00000004,0x00000001,0x00040020,0x00000020,
+  0x00000001,0x00000010,0x00040020,0x00000021,0x00000001,0x0000000e,0x0004002b,0x0000000c,
+  0x00000022,0x414eb852,0x0004002b,0x0000000c,0x00000023,0x3d6147ae,0x0004002b,0x0000000c,
+  0x00000024,0x3f870a3d,0x0006002c,0x0000000f,0x00000025,0x00000022,0x00000022,0x00000022,
+  0x0006002c,0x0000000f,0x00000026,0x00000023,0x00000023,0x00000023,0x0006002c,0x0000000f,
+  0x00000027,0x00000024,0x00000024,0x00000024,0x0004002b,0x0000000c,0x00000028,0x4019999a,
+  0x0004002b,0x0000000c,0x00000029,0x3d25aee6,0x0006002c,0x0000000f,0x0000002a,0x00000028,
+  0x00000028,0x00000028,0x0006002c,0x0000000f,0x0000002b,0x00000029,0x00000029,0x00000029,
+  0x00030031,0x00000011,0x00000009,0x0003001d,0x0000002c,0x0000001c,0x00040020,0x0000002d,
+  0x00000000,0x0000002c,0x0004003b,0x0000002d,0x00000006,0x00000000,0x0005001e,0x00000007,
+  0x0000000c,0x0000000d,0x0000000d,0x00040020,0x0000002e,0x00000009,0x00000007,0x0004003b,
+  0x0000002e,0x00000008,0x00000009,0x00040020,0x0000002f,0x00000009,0x0000000c,0x00040020,
+  0x00000030,0x00000009,0x0000000d,0x0004002b,0x0000000c,0x00000031,0x42a00000,0x00050036,
+  0x0000000a,0x00000002,0x00000000,0x0000000b,0x000200f8,0x00000032,0x00050041,0x00000021,
+  0x00000033,0x00000004,0x00000016,0x0004003d,0x0000000e,0x00000034,0x00000033,0x00050041,
+  0x00000030,0x00000035,0x00000008,0x00000016,0x0004003d,0x0000000d,0x00000036,0x00000035,
+  0x00050041,0x0000001d,0x00000037,0x00000006,0x00000036,0x0004003d,0x0000001c,0x00000038,
+  0x00000037,0x00050057,0x00000010,0x00000039,0x00000038,0x00000034,0x00050041,0x00000020,
+  0x0000003a,0x00000004,0x00000015,0x0004003d,0x00000010,0x0000003b,0x0000003a,0x0008004f,
+  0x0000000f,0x0000003c,0x0000003b,0x0000003b,0x00000000,0x00000001,0x00000002,0x00050051,
+  0x0000000c,0x0000003d,0x0000003b,0x00000003,0x0008004f,0x0000000f,0x0000003e,0x00000039,
+  0x00000039,0x00000000,0x00000001,0x00000002,0x00050051,0x0000000c,0x0000003f,0x00000039,
+  0x00000003,0x00050041,0x00000030,0x00000040,0x00000008,0x00000017,0x0004003d,0x0000000d,
+  0x00000041,0x00000040,0x000500c7,0x0000000d,0x00000042,0x00000041,0x00000019,0x000500ab,
+  0x00000011,0x00000043,0x00000042,0x00000018,0x00060050,0x00000012,0x00000044,0x00000043,
+  0x00000043,0x00000043,0x00050039,0x0000000f,0x00000045,0x00000005,0x0000003c,0x00050039,
+  0x0000000f,0x00000046,0x00000005,0x0000003e,0x000600a9,0x0000000f,0x00000047,0x00000044,
+  0x0000003e,0x00000046,0x0005008e,0x0000000f,0x00000048,0x00000045,0x0000003d,0x00060050,
+  0x00000012,0x00000049,0x00000009,0x00000009,0x00000009,0x000600a9,0x0000000f,0x0000004a,
+  0x00000049,0x00000048,0x00000045,0x00050085,0x0000000f,0x0000004b,0x0000004a,0x00000047,
+  0x00050085,0x0000000c,0x0000004c,0x0000003d,0x0000003f,0x00050041,0x0000002f,0x0000004d,
+  0x00000008,0x00000015,0x0004003d,0x0000000c,0x0000004e,0x0000004d,0x00050088,0x0000000c,
+  0x0000004f,0x0000004e,0x00000031,0x0005008e,0x0000000f,0x00000050,0x0000004b,0x0000004f,
+  0x00050050,0x00000010,0x00000051,0x00000050,0x0000004c,0x0003003e,0x00000003,0x00000051,
+  0x000100fd,0x00010038,0x00050036,0x0000000f,0x00000005,0x00000000,0x00000013,0x00030037,
+  0x0000000f,0x00000052,0x000200f8,0x00000053,0x00050088,0x0000000f,0x00000054,0x00000052,
+  0x00000025,0x00050081,0x0000000f,0x00000055,0x00000052,0x00000026,0x00050088,0x0000000f,
+  0x00000056,0x00000055,0x00000027,0x0007000c,0x0000000f,0x00000057,0x00000001,0x0000001a,
+  0x00000056,0x0000002a,0x000500ba,0x00000012,0x00000058,0x00000052,0x0000002b,0x000600a9,
+  0x0000000f,0x00000059,0x00000058,0x00000057,0x00000054,0x000200fe,0x00000059,0x00010038,
+];
+
+const __GLSL_SHADER_FRAG_BINDLESS_HDR10_SPV: [u32; 730] = [
+  0x07230203,0x00010000,0x00070000,0x0000007f,0x00000000,0x00020011,0x00000001,0x00020011,
+  0x0000001d,0x00020011,0x000014b6,0x0008000a,0x5f565053,0x5f545845,0x63736564,0x74706972,
+  0x695f726f,0x7865646e,0x00676e69,0x0006000b,0x00000001,0x4c534c47,0x6474732e,0x3035342e,
+  0x00000000,0x0003000e,0x00000000,0x00000001,0x0007000f,0x00000004,0x00000002,0x6e69616d,
+  0x00000000,0x00000003,0x00000004,0x00030010,0x00000002,0x00000007,0x00030003,0x00000002,
+  0x000001c2,0x00040005,0x00000002,0x6e69616d,0x00000000,0x00070005,0x00000005,0x62677273,
+  0x5f6f745f,0x656e696c,0x76287261,0x003b3366,0x00040005,0x00000003,0x6c6f4366,0x0000726f,
+  0x00050005,0x00000006,0x78655473,0x65727574,0x00000073,0x00030005,0x00000004,0x00006e49,
+  0x00060005,0x00000007,0x73755075,0x6e6f4368,0x6e617473,0x00000074,0x00060006,0x00000007,
+  0x00000000,0x70615075,0x68577265,0x00657469,0x00070006,0x00000007,0x00000001,0x78655475,
+  0x65727574,0x65646e49,0x00000078,0x00050006,0x00000007,0x00000002,0x616c4675,0x00007367,
+  0x00030005,0x00000008,0x00006370,0x00070005,0x00000009,0x4d455250,0x49544c55,0x5f594c50,
+  0x4f4c4f43,0x00000052,0x00040047,0x00000003,0x0000001e,0x00000000,0x00040047,0x00000006,
+  0x00000022,0x00000000,0x00040047,0x00000006,0x00000021,0x00000000,0x00040047,0x00000004,
+  0x0000001e,0x00000000,0x00050048,0x00000007,0x00000000,0x00000023,0x00000010,0x00050048,
+  0x00000007,0x00000001,0x00000023,0x00000014,0x00050048,0x00000007,0x00000002,0x00000023,
+  0x00000018,0x00030047,0x00000007,0x00000002,0x00040047,0x00000009,0x00000001,0x00000000,
+  0x00020013,0x0000000a,0x00030021,0x0000000b,0x0000000a,0x00030016,0x0000000c,0x00000020,
+  0x00040015,0x0000000d,0x00000020,0x00000000,0x00040017,0x0000000e,0x0000000c,0x00000002,
+  0x00040017,0x0000000f,0x0000000c,0x00000003,0x00040017,0x00000010,0x0000000c,0x00000004,
+  0x00020014,0x00000011,0x00040017,0x00000012,0x00000011,0x00000003,0x00040021,0x00000013,
+  0x0000000f,0x0000000f,0x00040015,0x00000014,0x00000020,0x00000001,0x0004002b,0x00000014,
+  0x00000015,0x00000000,0x0004002b,0x00000014,0x00000016,0x00000001,0x0004002b,0x00000014,
+  0x00000017,0x00000002,0x0004002b,0x0000000d,0x00000018,0x00000000,0x0004002b,0x0000000d,
+  0x00000019,0x00000001,0x00040020,0x0000001a,0x00000003,0x00000010,0x0004003b,0x0000001a,
+  0x00000003,0x00000003,0x00090019,0x0000001b,0x0000000c,0x00000001,0x00000000,0x00000000,
+  0x00000000,0x00000001,0x00000000,0x0003001b,0x0000001c,0x0000001b,0x00040020,0x0000001d,
+  0x00000000,0x0000001c,0x0004001e,0x0000001e,0x00000010,0x0000000e,0x00040020,0x0000001f,
+  0x00000001,0x0000001e,0x0004003b,0x0000001f,0x00000004,0x00000001,0x00040020,0x00000020,
+  0x00000001,0x00000010,0x00040020,0x00000021,0x00000001,0x0000000e,0x0004002b,0x0000000c,
+  0x00000022,0x414eb852,0x0004002b,0x0000000c,0x00000023,0x3d6147ae,0x0004002b,0x0000000c,
+  0x00000024,0x3f870a3d,0x0006002c,0x0000000f,0x00000025,0x00000022,0x00000022,0x00000022,
+  0x0006002c,0x0000000f,0x00000026,0x00000023,0x00000023,0x00000023,0x0006002c,0x0000000f,
+  0x00000027,0x00000024,0x00000024,0x00000024,0x0004002b,0x0000000c,0x00000028,0x4019999a,
+  0x0004002b,0x0000000c,0x00000029,0x3d25aee6,0x0006002c,0x0000000f,0x0000002a,0x00000028,
+  0x00000028,0x00000028,0x0006002c,0x0000000f,0x0000002b,0x00000029,0x00000029,0x00000029,
+  0x00030031,0x00000011,0x00000009,0x0003001d,0x0000002c,0x0000001c,0x00040020,0x0000002d,
+  0x00000000,0x0000002c,0x0004003b,0x0000002d,0x00000006,0x00000000,0x0005001e,0x00000007,
+  0x0000000c,0x0000000d,0x0000000d,0x00040020,0x0000002e,0x00000009,0x00000007,0x0004003b,
+  0x0000002e,0x00000008,0x00000009,0x00040020,0x0000002f,0x00000009,0x0000000c,0x00040020,
+  0x00000030,0x00000009,0x0000000d,0x00040018,0x00000031,0x0000000f,0x00000003,0x0004002b,
+  0x0000000c,0x00000032,0x3f209d8c,0x0004002b,0x0000000c,0x00000033,0x3d8d82ba,0x0004002b,
+  0x0000000c,0x00000034,0x3c8647ad,0x0004002b,0x0000000c,0x00000035,0x3ea897a6,0x0004002b,
+  0x0000000c,0x00000036,0x3f6b66f9,0x0004002b,0x0000000c,0x00000037,0x3db44044,0x0004002b,
+  0x0000000c,0x00000038,0x3d31699a,0x0004002b,0x0000000c,0x00000039,0x3c3a2454,0x0004002b,
+  0x0000000c,0x0000003a,0x3f6545b7,0x0006002c,0x0000000f,0x0000003b,0x00000032,0x00000033,
+  0x00000034,0x0006002c,0x0000000f,0x0000003c,0x00000035,0x00000036,0x00000037,0x0006002c,
+  0x0000000f,0x0000003d,0x00000038,0x00000039,0x0000003a,0x0006002c,0x00000031,0x0000003e,
+  0x0000003b,0x0000003c,0x0000003d,0x0004002b,0x0000000c,0x0000003f,0x461c4000,0x0004002b,
+  0x0000000c,0x00000040,0x00000000,0x0004002b,0x0000000c,0x00000041,0x3f800000,0x0006002c,
+  0x0000000f,0x00000042,0x00000040,0x00000040,0x00000040,0x0006002c,0x0000000f,0x00000043,
+  0x00000041,0x00000041,0x00000041,0x0004002b,0x0000000c,0x00000044,0x3e232000,0x0004002b,
+  0x0000000c,0x00000045,0x429db000,0x0004002b,0x0000000c,0x00000046,0x3f560000,0x0004002b,
+  0x0000000c,0x00000047,0x4196d000,0x0004002b,0x0000000c,0x00000048,0x41958000,0x0006002c,
+  0x0000000f,0x00000049,0x00000044,0x00000044,0x00000044,0x0006002c,0x0000000f,0x0000004a,
+  0x00000045,0x00000045,0x00000045,0x0006002c,0x0000000f,0x0000004b,0x00000046,0x00000046,
+  0x00000046,0x0006002c,0x0000000f,0x0000004c,0x00000047,0x00000047,0x00000047,0x0006002c,
+  0x0000000f,0x0000004d,0x00000048,0x00000048,0x00000048,0x00050036,0x0000000a,0x00000002,
+  0x00000000,0x0000000b,0x000200f8,0x0000004e,0x00050041,0x00000021,0x0000004f,0x00000004,
+  0x00000016,0x0004003d,0x0000000e,0x00000050,0x0000004f,0x00050041,0x00000030,0x00000051,
+  0x00000008,0x00000016,0x0004003d,0x0000000d,0x00000052,0x00000051,0x00050041,0x0000001d,
+  0x00000053,0x00000006,0x00000052,0x0004003d,0x0000001c,0x00000054,0x00000053,0x00050057,
+  0x00000010,0x00000055,0x00000054,0x00000050,0x00050041,0x00000020,0x00000056,0x00000004,
+  0x00000015,0x0004003d,0x00000010,0x00000057,0x00000056,0x0008004f,0x0000000f,0x00000058,
+  0x00000057,0x00000057,0x00000000,0x00000001,0x00000002,0x00050051,0x0000000c,0x00000059,
+  0x00000057,0x00000003,0x0008004f,0x0000000f,0x0000005a,0x00000055,0x00000055,0x00000000,
+  0x00000001,0x00000002,0x00050051,0x0000000c,0x0000005b,0x00000055,0x00000003,0x00050041,
+  0x00000030,0x0000005c,0x00000008,0x00000017,0x0004003d,0x0000000d,0x0000005d,0x0000005c,
+  0x000500c7,0x0000000d,0x0000005e,0x0000005d,0x00000019,0x000500ab,0x00000011,0x0000005f,
+  0x0000005e,0x00000018,0x00060050,0x00000012,0x00000060,0x0000005f,0x0000005f,0x0000005f,
+  0x00050039,0x0000000f,0x00000061,0x00000005,0x00000058,0x00050039,0x0000000f,0x00000062,
+  0x00000005,0x0000005a,0x000600a9,0x0000000f,0x00000063,0x00000060,0x0000005a,0x00000062,
+  0x0005008e,0x0000000f,0x00000064,0x00000061,0x00000059,0x00060050,0x00000012,0x00000065,
+  0x00000009,0x00000009,0x00000009,0x000600a9,0x0000000f,0x00000066,0x00000065,0x00000064,
+  0x00000061,0x00050085,0x0000000f,0x00000067,0x00000066,0x00000063,0x00050085,0x0000000c,
+  0x00000068,0x00000059,0x0000005b,0x00050041,0x0000002f,0x00000069,0x00000008,0x00000015,
+  0x0004003d,0x0000000c,0x0000006a,0x00000069,0x00050091,0x0000000f,0x0000006b,0x0000003e,
+  0x00000067,0x00050088,0x0000000c,0x0000006c,0x0000006a,0x0000003f,0x0005008e,0x0000000f,
+  0x0000006d,0x0000006b,0x0000006c,0x0008000c,0x0000000f,0x0000006e,0x00000001,0x0000002b,
+  0x0000006d,0x00000042,0x00000043,0x0007000c,0x0000000f,0x0000006f,0x00000001,0x0000001a,
+  0x0000006e,0x00000049,0x00050085,0x0000000f,0x00000070,0x0000004c,0x0000006f,0x00050081,
+  0x0000000f,0x00000071,0x0000004b,0x00000070,0x00050085,0x0000000f,0x00000072,0x0000004d,
+  0x0000006f,0x00050081,0x0000000f,0x00000073,0x00000043,0x00000072,0x00050088,0x0000000f,
+  0x00000074,0x00000071,0x00000073,0x0007000c,0x0000000f,0x00000075,0x00000001,0x0000001a,
+  0x00000074,0x0000004a,0x00050050,0x00000010,0x00000076,0x00000075,0x00000068,0x0003003e,
+  0x00000003,0x00000076,0x000100fd,0x00010038,0x00050036,0x0000000f,0x00000005,0x00000000,
+  0x00000013,0x00030037,0x0000000f,0x00000077,0x000200f8,0x00000078,0x00050088,0x0000000f,
+  0x00000079,0x00000077,0x00000025,0x00050081,0x0000000f,0x0000007a,0x00000077,0x00000026,
+  0x00050088,0x0000000f,0x0000007b,0x0000007a,0x00000027,0x0007000c,0x0000000f,0x0000007c,
+  0x00000001,0x0000001a,0x0000007b,0x0000002a,0x000500ba,0x00000012,0x0000007d,0x00000077,
+  0x0000002b,0x000600a9,0x0000000f,0x0000007e,0x0000007d,0x0000007c,0x00000079,0x000200fe,
+  0x0000007e,0x00010038,
+];
+
+/// The texture ID reserved for the font atlas.
+pub(crate) const FONT_TEXTURE_ID: usize = 0;
+
+/// The default brightness of the UI white in HDR output modes, the reference white of ITU-R BT.2408.
+const DEFAULT_PAPER_WHITE_NITS: f32 = 203.0;
+
+/// The size of the default ProggyClean font in logical pixels.
+const DEFAULT_FONT_SIZE: f32 = 13.0;
+
+/// The number of descriptor sets in the first texture descriptor pool.
+/// Every following pool doubles the capacity of the previous one.
+const INITIAL_TEXTURE_DESCRIPTOR_POOL_SIZE: usize = 16;
+
+/// The blend modes in the order of their pipelines.
+const BLEND_MODES: [HalaImGuiBlendMode; 2] = [HalaImGuiBlendMode::Straight, HalaImGuiBlendMode::Premultiplied];
 
-  font_sampler: hala_gfx::HalaSampler,
-  font_descriptor_set: hala_gfx::HalaDescriptorSet,
+/// The specialization constant ID of PREMULTIPLY_COLOR in the fragment shaders.
+const PREMULTIPLY_COLOR_SPEC_ID: u32 = 0;
+
+/// The texture flag pushed to the fragment shader: the texels are sampled as linear colors.
+const TEXTURE_FLAG_LINEAR: u32 = 1 << 0;
+
+/// The texture formats sampled as linear colors, the float formats and the sRGB formats decoded by the sampler.
+const LINEAR_TEXTURE_FORMATS: [hala_gfx::HalaFormat; 10] = [
+  hala_gfx::HalaFormat::R16_SFLOAT,
+  hala_gfx::HalaFormat::R16G16_SFLOAT,
+  hala_gfx::HalaFormat::R16G16B16A16_SFLOAT,
+  hala_gfx::HalaFormat::R32_SFLOAT,
+  hala_gfx::HalaFormat::R32G32_SFLOAT,
+  hala_gfx::HalaFormat::R32G32B32A32_SFLOAT,
+  hala_gfx::HalaFormat::B10G11R11_UFLOAT_PACK32,
+  hala_gfx::HalaFormat::R8_SRGB,
+  hala_gfx::HalaFormat::R8G8B8A8_SRGB,
+  hala_gfx::HalaFormat::B8G8R8A8_SRGB,
+];
+
+/// The binding of a texture in the shader.
+enum HalaImGuiTextureBinding {
+  /// The descriptor set of the texture.
+  DescriptorSet(hala_gfx::HalaDescriptorSet),
+  /// The element of the bindless texture array.
+  BindlessSlot(u32),
+}
+
+/// The texture registered in the ImGUI context.
+struct HalaImGuiTexture {
+  binding: HalaImGuiTextureBinding,
+  /// The image owned by the ImGUI context, None for the images owned by the user.
   #[allow(dead_code)]
-  font_descriptor_pool: Rc<RefCell<hala_gfx::HalaDescriptorPool>>,
+  image: Option<hala_gfx::HalaImage>,
+  /// The alpha mode of the texels, which selects the pipeline.
+  alpha_mode: HalaImGuiBlendMode,
+  /// The flags pushed to the fragment shader, see TEXTURE_FLAG_LINEAR.
+  flags: u32,
+}
+
+/// The encoding of the colors written by the ImGUI pass.
+/// The ImGUI style colors and the textures are sRGB encoded,
+/// except the textures of the float and sRGB formats which are sampled as linear colors.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum HalaImGuiOutputMode {
+  /// Linear for the sRGB color formats, scRGB for the FP16 swapchain, otherwise gamma.
+  #[default]
+  Auto,
+  /// Write the sRGB encoded colors as is, for the UNORM formats(including the 10-bit ones) presented as sRGB.
+  Gamma,
+  /// Linearize the vertex colors and the sRGB encoded texture samples, for the sRGB formats or the linear render targets.
+  Linear,
+  /// Linear BT.709 colors scaled so 1.0 is 80 nits, for the extended sRGB linear color space.
+  Scrgb,
+  /// PQ encoded BT.2020 colors, for the HDR10 ST2084 color space.
+  Hdr10,
+}
+
+/// The blend mode of the ImGUI pipeline, also the alpha mode of the textures.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum HalaImGuiBlendMode {
+  /// The colors are straight alpha.
+  #[default]
+  Straight,
+  /// The colors are premultiplied by alpha, e.g. the GPU render targets.
+  /// The straight alpha vertex colors are premultiplied in the shader, so the tint alpha, the style alpha
+  /// and the disabled widgets fade the texel colors as well as the alpha.
+  Premultiplied,
+}
+
+/// The source of the mouse events, so the widgets can adapt to the touch input.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum HalaImGuiMouseSource {
+  #[default]
+  Mouse,
+  /// The touch screen, the position is only valid while touching.
+  TouchScreen,
+  /// The pen or stylus, it can hover without touching.
+  /// winit only reports the stylus through the altitude angle on iOS,
+  /// so a stylus is reported as TouchScreen on X11, Wayland and Windows.
+  Pen,
+}
+
+/// The description of the ImGUI context.
+#[derive(Clone, Default)]
+pub struct HalaImGuiDesc {
+  /// Whether to enable the INI file.
+  pub enable_ini: bool,
+  /// The color format of the render target, None to render into the swapchain.
+  pub color_format: Option<hala_gfx::HalaFormat>,
+  /// The sample count of the render target.
+  /// None to use the multisample count of the context when rendering into the swapchain, or single sample for the offscreen render target.
+  pub sample_count: Option<hala_gfx::HalaSampleCountFlags>,
+  /// The number of frames in flight, None to use the number of the swapchain images.
+  /// It must be greater than 0, and the context must be drawn with HalaImGui::draw_in_frame when it is set.
+  pub frames_in_flight: Option<usize>,
+  /// The encoding of the output colors.
+  pub output_mode: HalaImGuiOutputMode,
+  /// The brightness of the UI white in nits for the HDR output modes, None to use 203 nits.
+  pub paper_white_nits: Option<f32>,
+  /// The SPIR-V of the vertex shader, None to use the built-in one.
+  /// It must keep the vertex inputs and the push constants of the built-in shader.
+  pub vert_spv: Option<Vec<u32>>,
+  /// The SPIR-V of the fragment shader, None to use the built-in one of the output mode.
+  /// It must keep the texture binding and the push constants of the built-in shader, bindless or not,
+  /// and premultiply the vertex colors by their alpha when the bool specialization constant 0(PREMULTIPLY_COLOR) is true.
+  pub frag_spv: Option<Vec<u32>>,
+  /// The blend mode of the font and the textures without an alpha mode.
+  pub blend_mode: HalaImGuiBlendMode,
+  /// The depth format of the render target.
+  /// None to use the depth format of the swapchain when rendering into the swapchain, or no depth for the offscreen render target.
+  pub depth_format: Option<hala_gfx::HalaFormat>,
+  /// The depth compare operation, None to disable the depth test.
+  pub depth_test: Option<hala_gfx::HalaCompareOp>,
+  /// Whether to write the depth.
+  pub depth_write: bool,
+  /// The capacity of the bindless texture array for the devices with descriptor indexing.
+  /// None to bind a descriptor set per texture.
+  /// The context creation fails with HalaImGuiError::UnsupportedBindlessTextures if the device lacks
+  /// runtimeDescriptorArray, descriptorBindingPartiallyBound, descriptorBindingUpdateUnusedWhilePending
+  /// or shaderSampledImageArrayNonUniformIndexing, or if the capacity exceeds maxPerStageDescriptorSamplers,
+  /// maxDescriptorSetSamplers or their update after bind counterparts.
+  pub bindless_textures: Option<usize>,
+  /// Whether to measure the GPU time of the ImGUI pass with the timestamp queries.
+  /// HalaImGui::reset_gpu_timer must be recorded before the render pass of HalaImGui::draw.
+  /// It is ignored with a warning if the graphics queue does not support the timestamps.
+  pub enable_gpu_timing: bool,
+}
+
+/// The image loaded and owned by the ImGUI context.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct HalaImGuiImage {
+  pub texture_id: imgui::TextureId,
+  pub width: u32,
+  pub height: u32,
+}
+
+/// The implementation of the image loaded by the ImGUI context.
+impl HalaImGuiImage {
+
+  /// Get the size of the image.
+  /// return: The size for imgui::Ui::image.
+  pub fn size(&self) -> [f32; 2] {
+    [self.width as f32, self.height as f32]
+  }
+
+}
+
+/// The consecutive draw commands merged into one draw call.
+struct HalaImGuiDrawBatch {
+  texture_id: usize,
+  clip_rect: [f32; 4],
+  first_index: u32,
+  index_count: u32,
+  vertex_offset: i32,
+}
 
-  pipeline: hala_gfx::HalaGraphicsPipeline,
+/// The implementation of the draw batch.
+impl HalaImGuiDrawBatch {
+
+  /// Merge the next draw command into the batch.
+  /// The commands merge when they share the texture, the clip rectangle and the vertex offset and their indices are contiguous.
+  /// param next: The batch of the next draw command.
+  /// return: Whether the command is merged.
+  fn merge(&mut self, next: &HalaImGuiDrawBatch) -> bool {
+    if self.texture_id != next.texture_id
+      || self.clip_rect != next.clip_rect
+      || self.vertex_offset != next.vertex_offset
+      || self.first_index + self.index_count != next.first_index
+    {
+      return false;
+    }
+
+    self.index_count += next.index_count;
+    true
+  }
+
+}
+
+/// The render state bound in the command buffer, None if unknown.
+struct HalaImGuiDrawState {
+  blend_mode: HalaImGuiBlendMode,
+  texture_id: Option<usize>,
+  texture_flags: Option<u32>,
+  clip_rect: Option<[f32; 4]>,
+}
+
+/// The implementation of the render state.
+impl HalaImGuiDrawState {
+
+  /// Create the state right after setup_render_state.
+  /// param blend_mode: The blend mode of the bound pipeline.
+  /// return: The state.
+  fn new(blend_mode: HalaImGuiBlendMode) -> Self {
+    Self {
+      blend_mode,
+      texture_id: None,
+      texture_flags: None,
+      clip_rect: None,
+    }
+  }
+
+}
+
+/// The resource retired until the frames in flight using it complete.
+/// Only the texture is reused, the others are held to be dropped later.
+#[allow(dead_code)]
+enum HalaImGuiRetiredResource {
+  Texture(HalaImGuiTexture),
+  Pipeline(hala_gfx::HalaGraphicsPipeline),
+  Buffer(HalaImGuiBuffer),
+  GpuTimer(HalaImGuiGpuTimer),
+}
+
+/// The draw callback invoked by HalaImGui::draw.
+/// param command_buffers: The command buffers.
+/// param index: The index.
+/// param clip_rect: The clip rectangle(min x, min y, max x, max y) in framebuffer space.
+/// return: The result.
+pub type HalaImGuiDrawCallback = dyn FnMut(&hala_gfx::HalaCommandBufferSet, usize, [f32; 4]) -> core::result::Result<(), hala_gfx::HalaGfxError>;
+
+/// The draw list which a draw callback is added to.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum HalaImGuiDrawListType {
+  /// The draw list of the current window.
+  Window,
+  /// The draw list drawn behind all windows.
+  Background,
+  /// The draw list drawn over all windows.
+  Foreground,
+}
 
-  font_image: Option<hala_gfx::HalaImage>,
-  vertex_buffers: Vec<Option<hala_gfx::HalaBuffer>>,
-  index_buffers: Vec<Option<hala_gfx::HalaBuffer>>,
+/// The renderer data which can be reached from the ImGUI IO while building the frame.
+struct HalaImGuiRendererData {
+  draw_callbacks: RefCell<Vec<Box<HalaImGuiDrawCallback>>>,
+  /// The characters requested for the on demand fonts while building the frame.
+  requested_glyphs: RefCell<BTreeSet<u32>>,
+  /// The IME data reported by ImGUI through the platform IME callback.
+  ime_data: Cell<Option<HalaImGuiImeData>>,
+  /// The source of the last mouse events, ImGUI has no io.MouseSource to keep it.
+  mouse_source: Cell<HalaImGuiMouseSource>,
+}
+
+/// The marker callback added to the draw lists for the Rust draw callbacks.
+/// The callback data is the index of the draw callback in HalaImGuiRendererData.
+/// HalaImGui::draw never calls it, it invokes the Rust closure instead.
+unsafe extern "C" fn hala_imgui_draw_callback(_parent_list: *const imgui::sys::ImDrawList, _cmd: *const imgui::sys::ImDrawCmd) {
+}
+
+/// Add the characters of the text to the glyphs requested for the on demand fonts of the current ImGUI context.
+/// It does nothing if there is no current context or it is not created by HalaImGui.
+/// param text: The text which will be rendered.
+pub(crate) fn request_glyphs(text: &str) {
+  unsafe {
+    if imgui::sys::igGetCurrentContext().is_null() {
+      return;
+    }
+    let io = imgui::sys::igGetIO();
+    let renderer_data = (*io).BackendRendererUserData as *const HalaImGuiRendererData;
+    if !renderer_data.is_null() {
+      (*renderer_data).requested_glyphs.borrow_mut().extend(text.chars().map(|c| c as u32));
+    }
+  }
+}
+
+/// The platform IME callback of the ImGUI IO.
+/// ImGUI calls it while ending the frame, only when the IME data of the focused text input changes.
+unsafe extern "C" fn hala_imgui_set_platform_ime_data(_viewport: *mut imgui::sys::ImGuiViewport, data: *mut imgui::sys::ImGuiPlatformImeData) {
+  let io = imgui::sys::igGetIO();
+  let renderer_data = (*io).BackendRendererUserData as *const HalaImGuiRendererData;
+  if renderer_data.is_null() || data.is_null() {
+    return;
+  }
+
+  let data = &*data;
+  let ime_data = if data.WantVisible {
+    Some(HalaImGuiImeData {
+      position: [data.InputPos.x, data.InputPos.y],
+      line_height: data.InputLineHeight,
+    })
+  } else {
+    None
+  };
+  (*renderer_data).ime_data.set(ime_data);
+}
+
+/// The ImGUI context.
+pub struct HalaImGui {
+  vert_shader: hala_gfx::HalaShader,
+  /// The fragment shaders indexed by the blend mode.
+  frag_shaders: Vec<hala_gfx::HalaShader>,
+  output_mode: HalaImGuiOutputMode,
+  paper_white_nits: f32,
+
+  samplers: HalaImGuiSamplers,
+
+  /// The descriptor set layout of the pipelines, each texture descriptor set owns an identical one.
+  texture_descriptor_set_layout: hala_gfx::HalaDescriptorSetLayout,
+  texture_descriptor_pools: Vec<Rc<RefCell<hala_gfx::HalaDescriptorPool>>>,
+  texture_descriptor_pool_capacity: usize,
+  texture_descriptor_pool_used: usize,
+  free_descriptor_sets: Vec<hala_gfx::HalaDescriptorSet>,
+  bindless_descriptor_set: Option<hala_gfx::HalaDescriptorSet>,
+  free_bindless_slots: Vec<u32>,
+  next_bindless_slot: u32,
+  retired_resources: VecDeque<(u64, HalaImGuiRetiredResource)>,
+  textures: HashMap<usize, HalaImGuiTexture>,
+  next_texture_id: usize,
+
+  /// The pipelines indexed by the blend mode.
+  pipelines: Vec<hala_gfx::HalaGraphicsPipeline>,
+
+  fonts: Vec<HalaImGuiFont>,
+  font_ids: Vec<imgui::FontId>,
+  default_font: usize,
+  on_demand_glyphs: BTreeSet<u32>,
+  fonts_dirty: bool,
+  scale_factor: f64,
+  vertex_buffers: Vec<HalaImGuiBuffer>,
+  index_buffers: Vec<HalaImGuiBuffer>,
+  vertex_data: Vec<imgui::DrawVert>,
+  index_data: Vec<imgui::DrawIdx>,
 
   imgui: imgui::Context,
+  renderer_data: Box<HalaImGuiRendererData>,
+  clipboard: HalaImGuiClipboard,
+  frames_in_flight: usize,
+  stats: HalaImGuiStats,
+  show_stats_overlay: bool,
+  mouse_cursor: Option<imgui::MouseCursor>,
+  /// The size of the framebuffer in physical pixels passed to begin_frame.
+  framebuffer_size: [u32; 2],
+  ime_preedit: HalaImGuiPreedit,
+  gamepad: HalaImGuiGamepad,
+  gpu_timer: Option<HalaImGuiGpuTimer>,
+  frame_serial: u64,
+  slot_serials: Vec<u64>,
+  completed_serial: u64,
+  swapchain_format: hala_gfx::HalaFormat,
+  desc: HalaImGuiDesc,
 
   pub(crate) vk_ctx: Rc<RefCell<hala_gfx::HalaContext>>,
 }
@@ -141,7 +1163,10 @@ impl Drop for HalaImGui {
 
   /// Drop the ImGUI context.
   fn drop(&mut self) {
-    self.font_image = None;
+    self.textures.clear();
+    self.retired_resources.clear();
+    self.free_descriptor_sets.clear();
+    self.bindless_descriptor_set = None;
 
     log::debug!("ImGUI context dropped.");
   }
@@ -285,182 +1310,221 @@ impl HalaImGui {
     Some(key)
   }
 
-  /// Create a new ImGUI context.
+  /// Get the mouse source of the touch.
+  /// Only the stylus reports the altitude angle, so it tells the pen from the finger.
+  /// winit fills the altitude angle on iOS only, the desktop platforms always get TouchScreen.
+  /// param touch: The winit touch.
+  /// return: The mouse source.
+  pub fn to_mouse_source(touch: &winit::event::Touch) -> HalaImGuiMouseSource {
+    match touch.force {
+      Some(winit::event::Force::Calibrated { altitude_angle: Some(_), .. }) => HalaImGuiMouseSource::Pen,
+      _ => HalaImGuiMouseSource::TouchScreen,
+    }
+  }
+
+  /// Convert the ImGUI mouse cursor to the winit cursor icon.
+  /// param cursor: The ImGUI mouse cursor.
+  /// return: The winit cursor icon.
+  pub fn to_cursor_icon(cursor: imgui::MouseCursor) -> CursorIcon {
+    match cursor {
+      imgui::MouseCursor::Arrow => CursorIcon::Default,
+      imgui::MouseCursor::TextInput => CursorIcon::Text,
+      imgui::MouseCursor::ResizeAll => CursorIcon::Move,
+      imgui::MouseCursor::ResizeNS => CursorIcon::NsResize,
+      imgui::MouseCursor::ResizeEW => CursorIcon::EwResize,
+      imgui::MouseCursor::ResizeNESW => CursorIcon::NeswResize,
+      imgui::MouseCursor::ResizeNWSE => CursorIcon::NwseResize,
+      imgui::MouseCursor::Hand => CursorIcon::Pointer,
+      imgui::MouseCursor::NotAllowed => CursorIcon::NotAllowed,
+    }
+  }
+
+  /// Create a new ImGUI context rendering into the swapchain.
   /// param vk_ctx The Vulkan context.
   /// param enable_ini Whether to enable the INI file.
   /// return The result of the creation.
   pub fn new(vk_ctx: Rc<RefCell<hala_gfx::HalaContext>>, enable_ini: bool) -> Result<Self> {
+    Self::with_desc(
+      vk_ctx,
+      &HalaImGuiDesc {
+        enable_ini,
+        ..Default::default()
+      },
+    )
+  }
+
+  /// Create a new ImGUI context with the description.
+  /// param vk_ctx The Vulkan context.
+  /// param desc The description.
+  /// return The result of the creation.
+  pub fn with_desc(vk_ctx: Rc<RefCell<hala_gfx::HalaContext>>, desc: &HalaImGuiDesc) -> Result<Self> {
+    if desc.frames_in_flight == Some(0) {
+      return Err(HalaImGuiError::InvalidFramesInFlight.into());
+    }
+
     let (
       vert_shader,
-      frag_shader,
-      font_descriptor_pool,
-      font_descriptor_set,
-      font_sampler,
-      pipeline,
-      num_of_images,
+      frag_shaders,
+      texture_descriptor_set_layout,
+      texture_descriptor_pool,
+      bindless_descriptor_set,
+      pipelines,
+      frames_in_flight,
+      swapchain_format,
+      output_mode,
     ) = {
       let context = vk_ctx.borrow();
+      if let Some(capacity) = desc.bindless_textures {
+        Self::check_bindless_features(&context, capacity)?;
+      }
 
-      let vert_code = unsafe {
-        std::slice::from_raw_parts(__GLSL_SHADER_VERT_SPV.as_ptr() as *const u8, __GLSL_SHADER_VERT_SPV.len() * 4)
-      };
-      let vert_shader = hala_gfx::HalaShader::new(
-        Rc::clone(&context.logical_device),
-        vert_code,
-        hala_gfx::HalaShaderStageFlags::VERTEX,
-        hala_gfx::HalaRayTracingShaderGroupType::GENERAL,
-        "imgui.vert.spv",
-      )?;
-
-      let frag_code = unsafe {
-        std::slice::from_raw_parts(__GLSL_SHADER_FRAG_SPV.as_ptr() as *const u8, __GLSL_SHADER_FRAG_SPV.len() * 4)
-      };
-      let frag_shader = hala_gfx::HalaShader::new(
-        Rc::clone(&context.logical_device),
-        frag_code,
-        hala_gfx::HalaShaderStageFlags::FRAGMENT,
-        hala_gfx::HalaRayTracingShaderGroupType::GENERAL,
-        "imgui.frag.spv",
-      )?;
-
-      let font_sampler = hala_gfx::HalaSampler::new(
-        Rc::clone(&context.logical_device),
-        (hala_gfx::HalaFilter::LINEAR, hala_gfx::HalaFilter::LINEAR),
-        hala_gfx::HalaSamplerMipmapMode::LINEAR,
-        (hala_gfx::HalaSamplerAddressMode::REPEAT, hala_gfx::HalaSamplerAddressMode::REPEAT, hala_gfx::HalaSamplerAddressMode::REPEAT),
-        0.0,
-        false,
-        1.0,
-        (-1000.0, 1000.0),
-        "imgui_font.sampler",
-      )?;
+      let vert_shader = Self::create_vert_shader(&context.logical_device, desc)?;
 
-      let font_descriptor_pool = Rc::new(
-        RefCell::new(
-          hala_gfx::HalaDescriptorPool::new(
-            Rc::clone(&context.logical_device),
-            &[
-              (hala_gfx::HalaDescriptorType::COMBINED_IMAGE_SAMPLER, 1),
-            ],
-            1,
-            "imgui_font.descpool",
-          )?
-        )
+      let output_mode = Self::resolve_output_mode(
+        desc.output_mode,
+        desc.color_format.unwrap_or(context.swapchain.format),
+        desc.color_format.is_none(),
       );
+      let frag_shaders = Self::create_frag_shaders(&context.logical_device, desc, output_mode)?;
 
-      let font_descriptor_set = hala_gfx::HalaDescriptorSet::new_static(
-        Rc::clone(&context.logical_device),
-        Rc::clone(&font_descriptor_pool),
-        hala_gfx::HalaDescriptorSetLayout::new(
-          Rc::clone(&context.logical_device),
-          &[
-            hala_gfx::HalaDescriptorSetLayoutBinding {
-              binding_index: 0,
-              descriptor_type: hala_gfx::HalaDescriptorType::COMBINED_IMAGE_SAMPLER,
-              descriptor_count: 1,
-              stage_flags: hala_gfx::HalaShaderStageFlags::FRAGMENT,
-              binding_flags: hala_gfx::HalaDescriptorBindingFlags::PARTIALLY_BOUND,
-            },
-          ],
-          "imgui_font.descsetlayout",
-        )?,
-        0,
-        "imgui_font.descset",
+      let texture_descriptor_set_layout = Self::create_texture_descriptor_set_layout(
+        &context.logical_device,
+        desc.bindless_textures,
+        "imgui_texture.descsetlayout",
       )?;
+      let (texture_descriptor_pool, bindless_descriptor_set) = match desc.bindless_textures {
+        Some(capacity) => {
+          // All textures live in one descriptor set.
+          let pool = Self::create_texture_descriptor_pool(&context.logical_device, 1, capacity, 0)?;
+          let descriptor_set = hala_gfx::HalaDescriptorSet::new_static(
+            Rc::clone(&context.logical_device),
+            Rc::clone(&pool),
+            Self::create_texture_descriptor_set_layout(
+              &context.logical_device,
+              desc.bindless_textures,
+              "imgui_bindless.descsetlayout",
+            )?,
+            0,
+            "imgui_bindless.descset",
+          )?;
+          (pool, Some(descriptor_set))
+        },
+        None => (
+          Self::create_texture_descriptor_pool(&context.logical_device, INITIAL_TEXTURE_DESCRIPTOR_POOL_SIZE, 1, 0)?,
+          None,
+        ),
+      };
 
-      let pipeline = hala_gfx::HalaGraphicsPipeline::new(
-        Rc::clone(&context.logical_device),
-        &context.swapchain,
-        &[&font_descriptor_set.layout],
-        hala_gfx::HalaPipelineCreateFlags::default(),
-        &[
-          // Position.
-          hala_gfx::HalaVertexInputAttributeDescription {
-            binding: 0,
-            location: 0,
-            offset: 0,
-            format: hala_gfx::HalaFormat::R32G32_SFLOAT,
-          },
-          // UV.
-          hala_gfx::HalaVertexInputAttributeDescription {
-            binding: 0,
-            location: 1,
-            offset: 8,
-            format: hala_gfx::HalaFormat::R32G32_SFLOAT,
-          },
-          // Color.
-          hala_gfx::HalaVertexInputAttributeDescription {
-            binding: 0,
-            location: 2,
-            offset: 16,
-            format: hala_gfx::HalaFormat::R8G8B8A8_UNORM,
-          },
-        ],
-        &[
-          hala_gfx::HalaVertexInputBindingDescription {
-            binding: 0,
-            stride: 20,
-            input_rate: hala_gfx::HalaVertexInputRate::VERTEX,
-          }
-        ],
-        &[
-          hala_gfx::HalaPushConstantRange {
-            stage_flags: hala_gfx::HalaShaderStageFlags::VERTEX,
-            offset: 0,
-            size: 16,
-          }
-        ],
-        hala_gfx::HalaPrimitiveTopology::TRIANGLE_LIST,
-        &hala_gfx::HalaBlendState::new(hala_gfx::HalaBlendFactor::SRC_ALPHA, hala_gfx::HalaBlendFactor::ONE_MINUS_SRC_ALPHA, hala_gfx::HalaBlendOp::ADD),
-        &hala_gfx::HalaBlendState::new(hala_gfx::HalaBlendFactor::ONE, hala_gfx::HalaBlendFactor::ONE_MINUS_SRC_ALPHA, hala_gfx::HalaBlendOp::ADD),
-        &hala_gfx::HalaRasterizerState::new(hala_gfx::HalaFrontFace::COUNTER_CLOCKWISE, hala_gfx::HalaCullModeFlags::NONE, hala_gfx::HalaPolygonMode::FILL, 1.0),
-        &hala_gfx::HalaMultisampleState::new(context.multisample_count, false, 0.0, &[], false, false),
-        &hala_gfx::HalaDepthState::new(false, false, hala_gfx::HalaCompareOp::NEVER),
-        None,
-        &[&vert_shader, &frag_shader],
-        &[hala_gfx::HalaDynamicState::VIEWPORT, hala_gfx::HalaDynamicState::SCISSOR],
-        None,
-        "imgui.pipeline",
+      let pipelines = Self::create_pipelines(
+        &context,
+        desc,
+        &texture_descriptor_set_layout,
+        &vert_shader,
+        &frag_shaders,
       )?;
 
       (
         vert_shader,
-        frag_shader,
-        font_descriptor_pool,
-        font_descriptor_set,
-        font_sampler,
-        pipeline,
-        context.swapchain.num_of_images,
+        frag_shaders,
+        texture_descriptor_set_layout,
+        texture_descriptor_pool,
+        bindless_descriptor_set,
+        pipelines,
+        desc.frames_in_flight.unwrap_or(context.swapchain.num_of_images),
+        context.swapchain.format,
+        output_mode,
       )
     };
 
     // Initialize ImGUI.
     let mut imgui = imgui::Context::create();
     imgui.style_mut().use_dark_colors();
-    if !enable_ini {
+    if !desc.enable_ini {
       imgui.set_ini_filename(None);
     }
     imgui.io_mut().config_flags = imgui::ConfigFlags::NAV_ENABLE_KEYBOARD | imgui::ConfigFlags::NAV_ENABLE_GAMEPAD;
+    // The vertex offset is passed to draw_indexed, so a draw list can have more than 65536 vertices with 16-bit indices.
+    imgui.io_mut().backend_flags |= imgui::BackendFlags::RENDERER_HAS_VTX_OFFSET;
+    // The mouse cursor is applied to the window by the application after each frame.
+    imgui.io_mut().backend_flags |= imgui::BackendFlags::HAS_MOUSE_CURSORS;
+
+    // The renderer data is boxed, so its address is stable after the ImGUI context is moved.
+    let renderer_data = Box::new(HalaImGuiRendererData {
+      draw_callbacks: RefCell::new(Vec::new()),
+      requested_glyphs: RefCell::new(BTreeSet::new()),
+      ime_data: Cell::new(None),
+      mouse_source: Cell::new(HalaImGuiMouseSource::Mouse),
+    });
+    unsafe {
+      let io = imgui.io_mut().raw_mut();
+      io.BackendRendererUserData = renderer_data.as_ref() as *const HalaImGuiRendererData as *mut std::ffi::c_void;
+      io.SetPlatformImeDataFn = Some(hala_imgui_set_platform_ime_data);
+    }
 
-    let mut vertex_buffers = Vec::with_capacity(num_of_images);
-    vertex_buffers.resize_with(num_of_images, || None);
-    let mut index_buffers = Vec::with_capacity(num_of_images);
-    index_buffers.resize_with(num_of_images, || None);
+    // Ctrl+C/Ctrl+V of the text widgets use the system clipboard out of the box.
+    let clipboard = HalaImGuiClipboard::with_system_or_memory();
+    imgui.set_clipboard_backend(clipboard.clone());
 
-    log::debug!("ImGUI context created.");
-    Ok(Self {
+    let (vertex_buffers, index_buffers) = Self::create_frame_buffers(frames_in_flight);
+
+    let mut desc = desc.clone();
+    if desc.enable_gpu_timing && !HalaImGuiGpuTimer::is_supported(&vk_ctx.borrow()) {
+      log::warn!("The graphics queue does not support the timestamps, the ImGUI GPU timing is disabled.");
+      desc.enable_gpu_timing = false;
+    }
+
+    let mut hala_imgui = Self {
       vk_ctx,
       vert_shader,
-      frag_shader,
-      font_descriptor_pool,
-      font_descriptor_set,
-      font_sampler,
-      pipeline,
-      font_image: None,
+      frag_shaders,
+      output_mode,
+      paper_white_nits: desc.paper_white_nits.unwrap_or(DEFAULT_PAPER_WHITE_NITS),
+      samplers: HalaImGuiSamplers::new(),
+      texture_descriptor_set_layout,
+      texture_descriptor_pools: vec![texture_descriptor_pool],
+      texture_descriptor_pool_capacity: INITIAL_TEXTURE_DESCRIPTOR_POOL_SIZE,
+      texture_descriptor_pool_used: 0,
+      free_descriptor_sets: Vec::new(),
+      bindless_descriptor_set,
+      free_bindless_slots: Vec::new(),
+      next_bindless_slot: 0,
+      retired_resources: VecDeque::new(),
+      textures: HashMap::new(),
+      next_texture_id: FONT_TEXTURE_ID + 1,
+      pipelines,
+      fonts: vec![HalaImGuiFont {
+        sources: vec![HalaImGuiFontSource::with_default(DEFAULT_FONT_SIZE)],
+      }],
+      font_ids: Vec::new(),
+      default_font: 0,
+      on_demand_glyphs: BTreeSet::new(),
+      fonts_dirty: true,
+      scale_factor: 1.0,
       vertex_buffers,
       index_buffers,
+      vertex_data: Vec::new(),
+      index_data: Vec::new(),
       imgui,
-    })
+      renderer_data,
+      clipboard,
+      frames_in_flight,
+      stats: HalaImGuiStats::default(),
+      show_stats_overlay: false,
+      mouse_cursor: Some(imgui::MouseCursor::Arrow),
+      framebuffer_size: [0, 0],
+      ime_preedit: HalaImGuiPreedit::default(),
+      gamepad: HalaImGuiGamepad::new(),
+      gpu_timer: None,
+      frame_serial: 0,
+      slot_serials: vec![0; frames_in_flight],
+      completed_serial: 0,
+      swapchain_format,
+      desc,
+    };
+    hala_imgui.rebuild_fonts();
+
+    log::debug!("ImGUI context created.");
+    Ok(hala_imgui)
   }
 
   /// Whether the imgui wants to capture the mouse.
@@ -469,29 +1533,158 @@ impl HalaImGui {
     self.imgui.io().want_capture_mouse
   }
 
+  /// Whether the imgui wants the text input, e.g. an InputText is active.
+  /// When true, the IME should be enabled.
+  pub fn want_text_input(&self) -> bool {
+    self.imgui.io().want_text_input
+  }
+
   /// Whether the imgui wants to capture the keyboard.
   /// When true, imgui will use the keyboard inputs, so do not dispatch them to your main
   pub fn want_capture_keyboard(&self) -> bool {
     self.imgui.io().want_capture_keyboard
   }
 
+  /// Rebuild the pipeline and the per-frame buffers if the swapchain changed.
+  /// return: The result.
+  fn sync_swapchain(&mut self) -> Result<()> {
+    let vk_ctx = Rc::clone(&self.vk_ctx);
+    let context = vk_ctx.borrow();
+
+    // The pipeline of an offscreen render target does not depend on the swapchain.
+    if self.desc.color_format.is_none() && context.swapchain.format != self.swapchain_format {
+      log::debug!("ImGUI pipeline rebuilt for the new swapchain format {:?}.", context.swapchain.format);
+      let output_mode = Self::resolve_output_mode(self.desc.output_mode, context.swapchain.format, true);
+      if output_mode != self.output_mode {
+        log::debug!("ImGUI output mode changed from {:?} to {:?}.", self.output_mode, output_mode);
+        self.frag_shaders = Self::create_frag_shaders(&context.logical_device, &self.desc, output_mode)?;
+        self.output_mode = output_mode;
+      }
+      let pipelines = Self::create_pipelines(
+        &context,
+        &self.desc,
+        &self.texture_descriptor_set_layout,
+        &self.vert_shader,
+        &self.frag_shaders,
+      )?;
+      let old_pipelines = std::mem::replace(&mut self.pipelines, pipelines);
+      for pipeline in old_pipelines {
+        self.retire(HalaImGuiRetiredResource::Pipeline(pipeline));
+      }
+      self.swapchain_format = context.swapchain.format;
+    }
+
+    // An explicit number of frames in flight does not depend on the swapchain.
+    let frames_in_flight = context.swapchain.num_of_images;
+    if self.desc.frames_in_flight.is_none() && frames_in_flight != self.frames_in_flight {
+      self.resize_frames_in_flight(frames_in_flight);
+    }
+
+    Ok(())
+  }
+
+  /// Set the scale factor of the window.
+  /// The font atlas is rebuilt at the new density when the scale factor changes.
+  /// param scale_factor: The scale factor from physical pixels to logical pixels.
+  pub fn set_scale_factor(&mut self, scale_factor: f64) {
+    if scale_factor <= 0.0 || scale_factor == self.scale_factor {
+      return;
+    }
+
+    log::debug!("ImGUI scale factor changed from {} to {}.", self.scale_factor, scale_factor);
+    self.scale_factor = scale_factor;
+    self.rebuild_fonts();
+  }
+
+  /// Get the encoding of the output colors.
+  /// return: The output mode resolved from the description and the color format, never Auto.
+  pub fn get_output_mode(&self) -> HalaImGuiOutputMode {
+    self.output_mode
+  }
+
+  /// Set the brightness of the UI white in the HDR output modes.
+  /// param paper_white_nits: The brightness in nits.
+  pub fn set_paper_white_nits(&mut self, paper_white_nits: f32) {
+    self.paper_white_nits = paper_white_nits.max(0.0);
+  }
+
+  /// Get the brightness of the UI white in the HDR output modes.
+  /// return: The brightness in nits.
+  pub fn get_paper_white_nits(&self) -> f32 {
+    self.paper_white_nits
+  }
+
+  /// Replace the clipboard backend used by the text widgets and the clipboard API.
+  /// param backend: The clipboard backend, e.g. HalaImGuiMemoryClipboard.
+  pub fn set_clipboard_backend<T: imgui::ClipboardBackend>(&mut self, backend: T) {
+    self.clipboard.set_backend(backend);
+  }
+
+  /// Get the clipboard text.
+  /// return: The text, None if the clipboard is empty or inaccessible.
+  pub fn get_clipboard_text(&self) -> Option<String> {
+    self.clipboard.get_text()
+  }
+
+  /// Set the clipboard text.
+  /// param text: The text.
+  pub fn set_clipboard_text(&self, text: &str) {
+    self.clipboard.set_text(text);
+  }
+
+  /// Get the render statistics of the last frame.
+  /// return: The stats.
+  pub fn get_stats(&self) -> &HalaImGuiStats {
+    &self.stats
+  }
+
+  /// Set whether to show the built-in stats overlay window after the UI function.
+  /// param show: Whether to show the overlay.
+  pub fn set_stats_overlay(&mut self, show: bool) {
+    self.show_stats_overlay = show;
+  }
+
+  /// Get the scale factor of the window.
+  /// return: The scale factor.
+  pub fn get_scale_factor(&self) -> f64 {
+    self.scale_factor
+  }
+
   /// Begin the ImGUI frame.
   /// param delta_time: The delta time.
-  /// param width: The width of the window.
-  /// param height: The height of the window.
+  /// param width: The width of the window in physical pixels.
+  /// param height: The height of the window in physical pixels.
   /// return: The result.
   pub fn begin_frame<F>(&mut self, delta_time: f64, width: u32, height: u32, mut ui_fn: F) -> Result<()>
     where F: FnMut(&mut imgui::Ui) -> Result<()>
   {
+    let scale_factor = self.scale_factor as f32;
     self.imgui.io_mut().delta_time = delta_time as f32;
-    self.imgui.io_mut().display_size = [width as f32, height as f32];
-    self.imgui.io_mut().display_framebuffer_scale = [1.0, 1.0];
+    self.imgui.io_mut().display_size = [width as f32 / scale_factor, height as f32 / scale_factor];
+    self.imgui.io_mut().display_framebuffer_scale = [scale_factor, scale_factor];
+    // Keep the physical size, display_size * framebuffer_scale may round down a pixel with the fractional scale factors.
+    self.framebuffer_size = [width, height];
+
+    self.sync_swapchain()?;
 
-    if self.font_image.is_none() {
+    self.gamepad.update(self.imgui.io_mut());
+
+    self.renderer_data.draw_callbacks.borrow_mut().clear();
+
+    if self.fonts_dirty {
       self.create_fonts_texture()?;
     }
 
-    ui_fn(self.imgui.new_frame())?;
+    let ui = self.imgui.new_frame();
+    ui_fn(ui)?;
+    if self.show_stats_overlay {
+      self.stats.show_overlay(ui);
+    }
+    self.mouse_cursor = ui.mouse_cursor();
+    // ImGUI reports the caret of the text input when the frame ends, so the preedit follows the last frame.
+    if let Some(ime_data) = self.renderer_data.ime_data.get() {
+      self.ime_preedit.draw(ui, &ime_data);
+    }
 
     Ok(())
   }
@@ -501,14 +1694,118 @@ impl HalaImGui {
   pub fn end_frame(&mut self) -> Result<()> {
     self.imgui.render();
 
+    // The draw data of the frame still uses the old atlas, the new one is uploaded in the next frame.
+    self.update_on_demand_glyphs();
+
+    Ok(())
+  }
+
+  /// Set the number of frames in flight.
+  /// The context must be created with an explicit number of frames in flight,
+  /// otherwise the number follows the swapchain images and it returns HalaImGuiError::SwapchainFramesInFlight.
+  /// param frames_in_flight: The number of frames in flight.
+  /// return: The result.
+  pub fn set_frames_in_flight(&mut self, frames_in_flight: usize) -> Result<()> {
+    if self.desc.frames_in_flight.is_none() {
+      return Err(HalaImGuiError::SwapchainFramesInFlight.into());
+    }
+    if frames_in_flight == 0 {
+      return Err(HalaImGuiError::InvalidFramesInFlight.into());
+    }
+
+    self.resize_frames_in_flight(frames_in_flight);
+
     Ok(())
   }
 
+  /// Recreate the per-frame resources for a new number of frames in flight.
+  /// param frames_in_flight: The number of frames in flight, greater than 0.
+  fn resize_frames_in_flight(&mut self, frames_in_flight: usize) {
+    if frames_in_flight == self.frames_in_flight {
+      return;
+    }
+
+    log::debug!("ImGUI frames in flight changed from {} to {}.", self.frames_in_flight, frames_in_flight);
+    let (vertex_buffers, index_buffers) = Self::create_frame_buffers(frames_in_flight);
+    let old_vertex_buffers = std::mem::replace(&mut self.vertex_buffers, vertex_buffers);
+    let old_index_buffers = std::mem::replace(&mut self.index_buffers, index_buffers);
+    for buffer in old_vertex_buffers.into_iter().chain(old_index_buffers) {
+      self.retire(HalaImGuiRetiredResource::Buffer(buffer));
+    }
+    self.slot_serials = vec![0; frames_in_flight];
+    if self.desc.frames_in_flight.is_some() {
+      self.desc.frames_in_flight = Some(frames_in_flight);
+    }
+    self.frames_in_flight = frames_in_flight;
+  }
+
   /// Draw the ImGUI.
+  /// The swapchain image index is used as the frame slot, see draw_in_frame.
+  /// The context with an explicit number of frames in flight must be drawn with draw_in_frame instead,
+  /// the image index does not tell which frame slot is free then.
   /// param index: The index.
   /// param command_buffers: The command buffers.
   /// return: The result.
   pub fn draw(&mut self, index: usize, command_buffers: &hala_gfx::HalaCommandBufferSet) -> core::result::Result<(), hala_gfx::HalaGfxError> {
+    if let Some(frames_in_flight) = self.desc.frames_in_flight {
+      return Err(HalaImGuiError::ExplicitFramesInFlight(frames_in_flight).into());
+    }
+
+    self.draw_in_frame(index % self.frames_in_flight, index, command_buffers)
+  }
+
+  /// Reset the GPU timer queries of a frame slot, it does nothing if the GPU timing is disabled.
+  /// The queries can only be reset outside of a render pass, so it must be recorded before the render pass
+  /// which draw or draw_in_frame is recorded in, otherwise the pass is not timed. draw_to_image calls it itself.
+  /// param frame_slot: The frame slot passed to draw_in_frame, or the index passed to draw.
+  /// param index: The index of the command buffer.
+  /// param command_buffers: The command buffers.
+  /// return: The result.
+  pub fn reset_gpu_timer(&mut self, frame_slot: usize, index: usize, command_buffers: &hala_gfx::HalaCommandBufferSet) -> core::result::Result<(), hala_gfx::HalaGfxError> {
+    if !self.desc.enable_gpu_timing {
+      return core::result::Result::Ok(());
+    }
+    let frame_slot = if self.desc.frames_in_flight.is_none() { frame_slot % self.frames_in_flight } else { frame_slot };
+    if frame_slot >= self.frames_in_flight {
+      return Err(HalaImGuiError::InvalidFrameSlot(frame_slot, self.frames_in_flight).into());
+    }
+
+    // The timer follows the number of frames in flight.
+    if self.gpu_timer.as_ref().is_none_or(|gpu_timer| gpu_timer.frames_in_flight() != self.frames_in_flight) {
+      let gpu_timer = HalaImGuiGpuTimer::new(&self.vk_ctx.borrow(), self.frames_in_flight)?;
+      if let Some(gpu_timer) = self.gpu_timer.replace(gpu_timer) {
+        self.retire(HalaImGuiRetiredResource::GpuTimer(gpu_timer));
+      }
+    }
+    if let Some(gpu_timer) = self.gpu_timer.as_mut() {
+      gpu_timer.reset(index, command_buffers, frame_slot);
+    }
+
+    core::result::Result::Ok(())
+  }
+
+  /// Draw the ImGUI with the resources of a frame slot.
+  /// The caller must have waited the fence of the last submission using the frame slot,
+  /// which tells the ImGUI context that the retired resources used by it can be destroyed.
+  /// param frame_slot: The frame slot in [0, frames in flight), HalaImGuiError::InvalidFrameSlot otherwise.
+  /// param index: The index of the command buffer.
+  /// param command_buffers: The command buffers.
+  /// return: The result.
+  pub fn draw_in_frame(&mut self, frame_slot: usize, index: usize, command_buffers: &hala_gfx::HalaCommandBufferSet) -> core::result::Result<(), hala_gfx::HalaGfxError> {
+    if frame_slot >= self.frames_in_flight {
+      return Err(HalaImGuiError::InvalidFrameSlot(frame_slot, self.frames_in_flight).into());
+    }
+
+    // The last submission of the slot is complete, so are the retired resources it used.
+    self.completed_serial = self.completed_serial.max(self.slot_serials[frame_slot]);
+    self.reclaim();
+    self.frame_serial += 1;
+    self.slot_serials[frame_slot] = self.frame_serial;
+    self.stats = HalaImGuiStats {
+      gpu_time_ms: self.gpu_timer.as_mut().and_then(|gpu_timer| gpu_timer.resolve(frame_slot)),
+      ..Default::default()
+    };
+
     // Get draw data.
     let draw_data = unsafe {
       let draw_data = imgui::sys::igGetDrawData() as *mut imgui::DrawData;
@@ -526,70 +1823,366 @@ impl HalaImGui {
 
     let context = self.vk_ctx.borrow();
 
-    // Create or resize the vertex/index buffers.
-    let vertex_buffer_size = total_vtx_count as u64 * std::mem::size_of::<imgui::DrawVert>() as u64;
-    let vertex_buffer = match self.vertex_buffers[index] {
-      Some(ref buffer) if buffer.size >= vertex_buffer_size => buffer,
-      _ => {
-        self.vertex_buffers[index] = Some(hala_gfx::HalaBuffer::new(
-          Rc::clone(&context.logical_device),
-          vertex_buffer_size,
-          hala_gfx::HalaBufferUsageFlags::VERTEX_BUFFER,
-          hala_gfx::HalaMemoryLocation::CpuToGpu,
-          &format!("imgui_vertex_{}.buffer", index),
-        )?);
-        self.vertex_buffers[index].as_ref().unwrap()
-      },
-    };
-    let index_buffer_size = total_idx_count as u64 * std::mem::size_of::<imgui::DrawIdx>() as u64;
-    let index_buffer = match self.index_buffers[index] {
-      Some(ref buffer) if buffer.size >= index_buffer_size => buffer,
-      _ => {
-        self.index_buffers[index] = Some(hala_gfx::HalaBuffer::new(
-          Rc::clone(&context.logical_device),
-          index_buffer_size,
-          hala_gfx::HalaBufferUsageFlags::INDEX_BUFFER,
-          hala_gfx::HalaMemoryLocation::CpuToGpu,
-          &format!("imgui_index_{}.buffer", index),
-        )?);
-        self.index_buffers[index].as_ref().unwrap()
-      },
-    };
-
-    // Fill the vertex/index buffers.
-    let mut vtx_offset = 0;
-    let mut idx_offset = 0;
+    // Gather the vertex/index data in one pass over the draw lists.
+    self.vertex_data.clear();
+    self.index_data.clear();
     for cmd_list in draw_data.draw_lists() {
-      let vtx_buffer = cmd_list.vtx_buffer();
-      let vtx_size = std::mem::size_of_val(vtx_buffer);
-      vertex_buffer.update_memory(
-        vtx_offset,
-        vtx_buffer
-      )?;
-      vtx_offset += vtx_size;
+      self.vertex_data.extend_from_slice(cmd_list.vtx_buffer());
+      self.index_data.extend_from_slice(cmd_list.idx_buffer());
+    }
 
-      let idx_buffer = cmd_list.idx_buffer();
-      let idx_size = std::mem::size_of_val(idx_buffer);
-      index_buffer.update_memory(
-        idx_offset,
-        idx_buffer,
-      )?;
-      idx_offset += idx_size;
+    // Grow or shrink the vertex/index buffers.
+    let mut stats = HalaImGuiStats {
+      draw_lists: draw_data.draw_lists_count() as u32,
+      vertices: total_vtx_count as u32,
+      indices: total_idx_count as u32,
+      uploaded_bytes: (std::mem::size_of_val(self.vertex_data.as_slice()) + std::mem::size_of_val(self.index_data.as_slice())) as u64,
+      ..self.stats
+    };
+    if self.vertex_buffers[frame_slot].reserve(
+      &context.logical_device,
+      std::mem::size_of_val(self.vertex_data.as_slice()) as u64,
+    )? {
+      stats.buffer_reallocations += 1;
     }
+    if self.index_buffers[frame_slot].reserve(
+      &context.logical_device,
+      std::mem::size_of_val(self.index_data.as_slice()) as u64,
+    )? {
+      stats.buffer_reallocations += 1;
+    }
+    let vertex_buffer = self.vertex_buffers[frame_slot].buffer().unwrap();
+    let index_buffer = self.index_buffers[frame_slot].buffer().unwrap();
+
+    // Fill the vertex/index buffers with one copy each.
+    vertex_buffer.update_memory(0, &self.vertex_data)?;
+    index_buffer.update_memory(0, &self.index_data)?;
 
     // Will project scissor/clipping rectangles into framebuffer space
-    let (clip_off, clip_scale, fb_size) = (
+    let (clip_off, clip_scale) = (
       draw_data.display_pos,        // (0,0) unless using multi-viewports
       draw_data.framebuffer_scale,  // (1,1) unless using retina display which are often (2,2)
-      self.imgui.io().display_size,
     );
+    let fb_size = [
+      self.framebuffer_size[0] as f32,
+      self.framebuffer_size[1] as f32,
+    ];
+
+    if let Some(gpu_timer) = self.gpu_timer.as_ref() {
+      gpu_timer.begin(index, command_buffers, frame_slot);
+    }
+
+    // Setup desired render state.
+    let mut state = HalaImGuiDrawState::new(self.desc.blend_mode);
+    self.setup_render_state(index, command_buffers, draw_data, vertex_buffer, index_buffer);
+
+    // Render command list.
+    // The consecutive commands with the same texture and clip rectangle and contiguous indices are merged into one draw call.
+    let mut batch: Option<HalaImGuiDrawBatch> = None;
+    unsafe {
+      let mut vtx_offset = 0;
+      let mut idx_offset = 0;
+      for cmd_list in draw_data.draw_lists() {
+        for cmd in cmd_list.commands() {
+          match cmd {
+            imgui::DrawCmd::ResetRenderState => {
+              if let Some(batch) = batch.take() {
+                self.draw_batch(index, command_buffers, &batch, &mut state, &mut stats);
+              }
+              self.setup_render_state(index, command_buffers, draw_data, vertex_buffer, index_buffer);
+              state = HalaImGuiDrawState::new(self.desc.blend_mode);
+            },
+            imgui::DrawCmd::RawCallback { callback, raw_cmd } => {
+              if let Some(batch) = batch.take() {
+                self.draw_batch(index, command_buffers, &batch, &mut state, &mut stats);
+              }
+
+              if callback as usize == hala_imgui_draw_callback as usize {
+                let clip_rect = (*raw_cmd).ClipRect;
+                if let Some(clip_rect) = Self::project_clip_rect(
+                  [clip_rect.x, clip_rect.y, clip_rect.z, clip_rect.w],
+                  clip_off,
+                  clip_scale,
+                  fb_size,
+                ) {
+                  Self::set_clip_rect(index, command_buffers, &clip_rect);
+
+                  let callback_index = (*raw_cmd).UserCallbackData as usize;
+                  let mut draw_callbacks = self.renderer_data.draw_callbacks.borrow_mut();
+                  if let Some(draw_callback) = draw_callbacks.get_mut(callback_index) {
+                    draw_callback(command_buffers, index, clip_rect)?;
+                  }
+                }
+              } else {
+                callback(cmd_list.raw(), raw_cmd);
+              }
+
+              // The callback may change any state, so restore ours.
+              self.setup_render_state(index, command_buffers, draw_data, vertex_buffer, index_buffer);
+              state = HalaImGuiDrawState::new(self.desc.blend_mode);
+            },
+            imgui::DrawCmd::Elements { count, cmd_params } => {
+              stats.draw_commands += 1;
+
+              // Project scissor/clipping rectangles into framebuffer space.
+              let clip_rect = match Self::project_clip_rect(cmd_params.clip_rect, clip_off, clip_scale, fb_size) {
+                Some(clip_rect) => clip_rect,
+                None => continue,
+              };
+
+              // Unknown texture IDs fall back to the font atlas.
+              let texture_id = if self.textures.contains_key(&cmd_params.texture_id.id()) {
+                cmd_params.texture_id.id()
+              } else if self.textures.contains_key(&FONT_TEXTURE_ID) {
+                FONT_TEXTURE_ID
+              } else {
+                continue;
+              };
+              // The draw lists are packed into one vertex/index buffer, and a draw list over 64K vertices
+              // is split by the command vertex offset, see RENDERER_HAS_VTX_OFFSET.
+              let first_index = cmd_params.idx_offset as u32 + idx_offset;
+              let vertex_offset = cmd_params.vtx_offset as i32 + vtx_offset;
+
+              let next = HalaImGuiDrawBatch {
+                texture_id,
+                clip_rect,
+                first_index,
+                index_count: count as u32,
+                vertex_offset,
+              };
+              if batch.as_mut().is_some_and(|batch| batch.merge(&next)) {
+                continue;
+              }
+              if let Some(batch) = batch.replace(next) {
+                self.draw_batch(index, command_buffers, &batch, &mut state, &mut stats);
+              }
+            }
+          }
+        }
+
+        idx_offset += cmd_list.idx_buffer().len() as u32;
+        vtx_offset += cmd_list.vtx_buffer().len() as i32;
+      }
+    }
+    if let Some(batch) = batch.take() {
+      self.draw_batch(index, command_buffers, &batch, &mut state, &mut stats);
+    }
+
+    // Note: at this point both vkCmdSetViewport() and vkCmdSetScissor() have been called.
+    // Our last values will leak into user/application rendering.
+    command_buffers.set_scissor(
+      index,
+      0,
+      &[
+        (0, 0, self.framebuffer_size[0], self.framebuffer_size[1])
+      ],
+    );
+    if let Some(gpu_timer) = self.gpu_timer.as_mut() {
+      gpu_timer.end(index, command_buffers, frame_slot);
+    }
+    self.stats = stats;
+
+    core::result::Result::Ok(())
+  }
+
+  /// Draw a batch of the merged draw commands, skipping the redundant state changes.
+  /// param index: The index of the command buffer.
+  /// param command_buffers: The command buffers.
+  /// param batch: The batch.
+  /// param state: The state bound in the command buffer.
+  /// param stats: The stats of this frame.
+  fn draw_batch(
+    &self,
+    index: usize,
+    command_buffers: &hala_gfx::HalaCommandBufferSet,
+    batch: &HalaImGuiDrawBatch,
+    state: &mut HalaImGuiDrawState,
+    stats: &mut HalaImGuiStats,
+  ) {
+    // Apply scissor/clipping rectangle.
+    if state.clip_rect != Some(batch.clip_rect) {
+      Self::set_clip_rect(index, command_buffers, &batch.clip_rect);
+      state.clip_rect = Some(batch.clip_rect);
+      stats.scissor_sets += 1;
+    }
+
+    // Switch the pipeline when the alpha mode changes, the layouts are the same so the push constants and the descriptor sets are kept.
+    let texture = &self.textures[&batch.texture_id];
+    if texture.alpha_mode != state.blend_mode {
+      state.blend_mode = texture.alpha_mode;
+      command_buffers.bind_graphics_pipeline(index, &self.pipelines[state.blend_mode as usize]);
+    }
+
+    // Bind DescriptorSet with font or user texture.
+    if state.texture_id != Some(batch.texture_id) {
+      match &texture.binding {
+        HalaImGuiTextureBinding::DescriptorSet(descriptor_set) => {
+          command_buffers.bind_graphics_descriptor_sets(
+            index,
+            &self.pipelines[state.blend_mode as usize],
+            0,
+            &[descriptor_set],
+            &[],
+          );
+        },
+        HalaImGuiTextureBinding::BindlessSlot(slot) => {
+          // The texture index is pushed as the bits of a float after the paper white.
+          command_buffers.push_constants_f32(
+            index,
+            self.pipelines[state.blend_mode as usize].layout,
+            hala_gfx::HalaShaderStageFlags::FRAGMENT,
+            20,
+            &[f32::from_bits(*slot)],
+          );
+        },
+      }
+      state.texture_id = Some(batch.texture_id);
+      stats.descriptor_binds += 1;
+    }
+    if state.texture_flags != Some(texture.flags) {
+      command_buffers.push_constants_f32(
+        index,
+        self.pipelines[state.blend_mode as usize].layout,
+        hala_gfx::HalaShaderStageFlags::FRAGMENT,
+        24,
+        &[f32::from_bits(texture.flags)],
+      );
+      state.texture_flags = Some(texture.flags);
+    }
+
+    // Draw.
+    command_buffers.draw_indexed(
+      index,
+      batch.index_count,
+      1,
+      batch.first_index,
+      batch.vertex_offset,
+      0,
+    );
+    stats.draw_calls += 1;
+  }
+
+  /// Draw the ImGUI into an offscreen render target.
+  /// The context must be created with the color format and the sample count of the target and without a depth format,
+  /// and begin_frame must be called with the size of the target.
+  /// Only single sample targets are supported: the pass has no resolve attachment,
+  /// so a multisample target would be left unresolved and can not be sampled as a texture.
+  /// The target is left in SHADER_READ_ONLY_OPTIMAL layout, so it can be sampled or registered as a texture.
+  /// param frame_slot: The frame slot in [0, frames in flight), see draw_in_frame.
+  /// param index: The index of the command buffer.
+  /// param command_buffers: The command buffers.
+  /// param target: The render target.
+  /// param clear_color: The clear color, None to keep the content of the target which must be in SHADER_READ_ONLY_OPTIMAL layout.
+  /// return: The result.
+  pub fn draw_to_image(
+    &mut self,
+    frame_slot: usize,
+    index: usize,
+    command_buffers: &hala_gfx::HalaCommandBufferSet,
+    target: &hala_gfx::HalaImage,
+    clear_color: Option<[f32; 4]>,
+  ) -> core::result::Result<(), hala_gfx::HalaGfxError> {
+    // Check the slot before the pass is recorded, draw_in_frame would fail inside it.
+    if frame_slot >= self.frames_in_flight {
+      return Err(HalaImGuiError::InvalidFrameSlot(frame_slot, self.frames_in_flight).into());
+    }
+    self.reset_gpu_timer(frame_slot, index, command_buffers)?;
+
+    let (old_layout, src_stage_mask, src_access_mask) = if clear_color.is_some() {
+      (
+        hala_gfx::HalaImageLayout::UNDEFINED,
+        hala_gfx::HalaPipelineStageFlags2::TOP_OF_PIPE,
+        hala_gfx::HalaAccessFlags2::NONE,
+      )
+    } else {
+      (
+        hala_gfx::HalaImageLayout::SHADER_READ_ONLY_OPTIMAL,
+        hala_gfx::HalaPipelineStageFlags2::FRAGMENT_SHADER,
+        hala_gfx::HalaAccessFlags2::SHADER_READ,
+      )
+    };
+    command_buffers.set_image_barriers(
+      index,
+      &[hala_gfx::HalaImageBarrierInfo {
+        old_layout,
+        new_layout: hala_gfx::HalaImageLayout::COLOR_ATTACHMENT_OPTIMAL,
+        src_access_mask,
+        dst_access_mask: hala_gfx::HalaAccessFlags2::COLOR_ATTACHMENT_READ | hala_gfx::HalaAccessFlags2::COLOR_ATTACHMENT_WRITE,
+        src_stage_mask,
+        dst_stage_mask: hala_gfx::HalaPipelineStageFlags2::COLOR_ATTACHMENT_OUTPUT,
+        aspect_mask: hala_gfx::HalaImageAspectFlags::COLOR,
+        image: target.raw,
+        ..Default::default()
+      }],
+    );
+
+    command_buffers.begin_rendering_with(
+      index,
+      &[target],
+      None,
+      (0, 0, target.extent.width, target.extent.height),
+      clear_color,
+      None,
+      None,
+    );
+    self.draw_in_frame(frame_slot, index, command_buffers)?;
+    command_buffers.end_rendering(index);
+
+    command_buffers.set_image_barriers(
+      index,
+      &[hala_gfx::HalaImageBarrierInfo {
+        old_layout: hala_gfx::HalaImageLayout::COLOR_ATTACHMENT_OPTIMAL,
+        new_layout: hala_gfx::HalaImageLayout::SHADER_READ_ONLY_OPTIMAL,
+        src_access_mask: hala_gfx::HalaAccessFlags2::COLOR_ATTACHMENT_WRITE,
+        dst_access_mask: hala_gfx::HalaAccessFlags2::SHADER_READ,
+        src_stage_mask: hala_gfx::HalaPipelineStageFlags2::COLOR_ATTACHMENT_OUTPUT,
+        dst_stage_mask: hala_gfx::HalaPipelineStageFlags2::FRAGMENT_SHADER,
+        aspect_mask: hala_gfx::HalaImageAspectFlags::COLOR,
+        image: target.raw,
+        ..Default::default()
+      }],
+    );
+
+    core::result::Result::Ok(())
+  }
+
+  /// Setup the render state for drawing the ImGUI.
+  /// It binds the pipeline and the vertex/index buffers, sets the viewport and the scissor
+  /// and pushes the scale/translation constants.
+  /// param index: The index.
+  /// param command_buffers: The command buffers.
+  /// param draw_data: The draw data.
+  /// param vertex_buffer: The vertex buffer.
+  /// param index_buffer: The index buffer.
+  fn setup_render_state(
+    &self,
+    index: usize,
+    command_buffers: &hala_gfx::HalaCommandBufferSet,
+    draw_data: &imgui::DrawData,
+    vertex_buffer: &hala_gfx::HalaBuffer,
+    index_buffer: &hala_gfx::HalaBuffer,
+  ) {
+    // The viewport covers the framebuffer in physical pixels.
+    let fb_size = self.framebuffer_size;
 
     // Bind pipeline.
-    command_buffers.bind_graphics_pipeline(index, &self.pipeline);
+    let pipeline = &self.pipelines[self.desc.blend_mode as usize];
+    command_buffers.bind_graphics_pipeline(index, pipeline);
+
+    // The bindless texture array is bound once, the textures are selected by the push constant.
+    if let Some(descriptor_set) = self.bindless_descriptor_set.as_ref() {
+      command_buffers.bind_graphics_descriptor_sets(
+        index,
+        pipeline,
+        0,
+        &[descriptor_set],
+        &[],
+      );
+    }
 
     // Bind vertex/index buffers.
     command_buffers.bind_vertex_buffers(index, 0, &[vertex_buffer], &[0]);
-    command_buffers.bind_index_buffers(index, &[index_buffer], &[0], hala_gfx::HalaIndexType::UINT16);
+    command_buffers.bind_index_buffers(index, &[index_buffer], &[0], Self::index_type());
 
     // Set viewport.
     command_buffers.set_viewport(
@@ -598,8 +2191,8 @@ impl HalaImGui {
       &[(
         0.0,
         0.0,
-        fb_size[0],
-        fb_size[1],
+        fb_size[0] as f32,
+        fb_size[1] as f32,
         0.0,
         1.0,
       )],
@@ -608,115 +2201,197 @@ impl HalaImGui {
       index,
       0,
       &[
-        (0, 0, fb_size[0] as u32, fb_size[1] as u32),
+        (0, 0, fb_size[0], fb_size[1]),
       ],
     );
 
     // Setup scale and translation:
     // Our visible imgui space lies from draw_data->DisplayPps (top left) to draw_data->DisplayPos+data_data->DisplaySize (bottom right). DisplayPos is (0,0) for single viewport apps.
     let scale = [
-      2.0 / fb_size[0],
-      2.0 / fb_size[1],
+      2.0 / draw_data.display_size[0],
+      2.0 / draw_data.display_size[1],
     ];
     let translate = [
-      -1.0 - clip_off[0] * scale[0],
-      -1.0 - clip_off[1] * scale[1],
+      -1.0 - draw_data.display_pos[0] * scale[0],
+      -1.0 - draw_data.display_pos[1] * scale[1],
     ];
     command_buffers.push_constants_f32(
       index,
-      self.pipeline.layout,
+      pipeline.layout,
       hala_gfx::HalaShaderStageFlags::VERTEX,
       0,
       &scale,
     );
     command_buffers.push_constants_f32(
       index,
-      self.pipeline.layout,
+      pipeline.layout,
       hala_gfx::HalaShaderStageFlags::VERTEX,
       std::mem::size_of_val(&scale) as u32,
       &translate,
     );
+    if matches!(self.output_mode, HalaImGuiOutputMode::Scrgb | HalaImGuiOutputMode::Hdr10) {
+      command_buffers.push_constants_f32(
+        index,
+        pipeline.layout,
+        hala_gfx::HalaShaderStageFlags::FRAGMENT,
+        16,
+        &[self.paper_white_nits],
+      );
+    }
+  }
 
-    // Render command list.
-    unsafe {
-      let mut vtx_offset = 0;
-      let mut idx_offset = 0;
-      for cmd_list in draw_data.draw_lists() {
-        for cmd in cmd_list.commands() {
-          match cmd {
-            imgui::DrawCmd::ResetRenderState => {
-              // TODO: Reset render state.
-            },
-            imgui::DrawCmd::RawCallback { callback, raw_cmd } => {
-              callback(cmd_list.raw(), raw_cmd);
-            },
-            imgui::DrawCmd::Elements { count, cmd_params } => {
-              // Project scissor/clipping rectangles into framebuffer space.
-              let mut clip_min = [
-                (cmd_params.clip_rect[0] - clip_off[0]) * clip_scale[0],
-                (cmd_params.clip_rect[1] - clip_off[1]) * clip_scale[1],
-              ];
-              let mut clip_max = [
-                (cmd_params.clip_rect[2] - clip_off[0]) * clip_scale[0],
-                (cmd_params.clip_rect[3] - clip_off[1]) * clip_scale[1],
-              ];
-
-              // Clamp to viewport as vkCmdSetScissor() won't accept values that are off bounds.
-              if clip_min[0] < 0.0 { clip_min[0] = 0.0; }
-              if clip_min[1] < 0.0 { clip_min[1] = 0.0; }
-              if clip_max[0] > fb_size[0] { clip_max[0] = fb_size[0]; }
-              if clip_max[1] > fb_size[1] { clip_max[1] = fb_size[1]; }
-              if clip_max[0] <= clip_min[0] || clip_max[1] <= clip_min[1] {
-                continue;
-              }
-
-              // Apply scissor/clipping rectangle.
-              command_buffers.set_scissor(
-                index,
-                0,
-                &[
-                  (clip_min[0] as i32, clip_min[1] as i32, (clip_max[0] - clip_min[0]) as u32, (clip_max[1] - clip_min[1]) as u32)
-                ],
-              );
-
-              // Bind DescriptorSet with font or user texture.
-              command_buffers.bind_graphics_descriptor_sets(
-                index,
-                &self.pipeline,
-                0,
-                &[&self.font_descriptor_set],
-                &[],
-              );
-
-              // Draw.
-              command_buffers.draw_indexed(
-                index,
-                count as u32,
-                1,
-                cmd_params.idx_offset as u32 + idx_offset,
-                cmd_params.vtx_offset as i32 + vtx_offset,
-                0,
-              );
-            }
-          }
-        }
+  /// Project the clip rectangle into framebuffer space.
+  /// param clip_rect: The clip rectangle(min x, min y, max x, max y) in ImGUI space.
+  /// param clip_off: The display position of the draw data.
+  /// param clip_scale: The framebuffer scale of the draw data.
+  /// param fb_size: The framebuffer size.
+  /// return: The clip rectangle in framebuffer space, or None if it is empty.
+  fn project_clip_rect(clip_rect: [f32; 4], clip_off: [f32; 2], clip_scale: [f32; 2], fb_size: [f32; 2]) -> Option<[f32; 4]> {
+    let mut clip_min = [
+      (clip_rect[0] - clip_off[0]) * clip_scale[0],
+      (clip_rect[1] - clip_off[1]) * clip_scale[1],
+    ];
+    let mut clip_max = [
+      (clip_rect[2] - clip_off[0]) * clip_scale[0],
+      (clip_rect[3] - clip_off[1]) * clip_scale[1],
+    ];
 
-        idx_offset += cmd_list.idx_buffer().len() as u32;
-        vtx_offset += cmd_list.vtx_buffer().len() as i32;
-      }
+    // Clamp to viewport as vkCmdSetScissor() won't accept values that are off bounds.
+    if clip_min[0] < 0.0 { clip_min[0] = 0.0; }
+    if clip_min[1] < 0.0 { clip_min[1] = 0.0; }
+    if clip_max[0] > fb_size[0] { clip_max[0] = fb_size[0]; }
+    if clip_max[1] > fb_size[1] { clip_max[1] = fb_size[1]; }
+    if clip_max[0] <= clip_min[0] || clip_max[1] <= clip_min[1] {
+      return None;
     }
 
-    // Note: at this point both vkCmdSetViewport() and vkCmdSetScissor() have been called.
-    // Our last values will leak into user/application rendering.
+    Some([clip_min[0], clip_min[1], clip_max[0], clip_max[1]])
+  }
+
+  /// Apply the clip rectangle as the scissor.
+  /// param index: The index.
+  /// param command_buffers: The command buffers.
+  /// param clip_rect: The clip rectangle(min x, min y, max x, max y) in framebuffer space.
+  fn set_clip_rect(index: usize, command_buffers: &hala_gfx::HalaCommandBufferSet, clip_rect: &[f32; 4]) {
     command_buffers.set_scissor(
       index,
       0,
       &[
-        (0, 0, fb_size[0] as u32, fb_size[1] as u32)
+        (clip_rect[0] as i32, clip_rect[1] as i32, (clip_rect[2] - clip_rect[0]) as u32, (clip_rect[3] - clip_rect[1]) as u32)
       ],
     );
+  }
 
-    core::result::Result::Ok(())
+  /// Add a draw callback to a draw list of the current frame.
+  /// It must be called inside the UI function of begin_frame.
+  /// The callback is invoked by draw with the clip rectangle applied as the scissor,
+  /// and the ImGUI render state is restored after it returns.
+  /// param ui: The UI of the current frame.
+  /// param draw_list_type: The draw list which the callback is added to.
+  /// param callback: The callback.
+  pub fn add_draw_callback<F>(_ui: &imgui::Ui, draw_list_type: HalaImGuiDrawListType, callback: F)
+    where F: FnMut(&hala_gfx::HalaCommandBufferSet, usize, [f32; 4]) -> core::result::Result<(), hala_gfx::HalaGfxError> + 'static
+  {
+    unsafe {
+      let io = imgui::sys::igGetIO();
+      let renderer_data = (*io).BackendRendererUserData as *const HalaImGuiRendererData;
+      if renderer_data.is_null() {
+        log::warn!("The ImGUI renderer is not initialized, the draw callback is ignored.");
+        return;
+      }
+
+      let mut draw_callbacks = (*renderer_data).draw_callbacks.borrow_mut();
+      let callback_index = draw_callbacks.len();
+      draw_callbacks.push(Box::new(callback));
+
+      let draw_list = match draw_list_type {
+        HalaImGuiDrawListType::Window => imgui::sys::igGetWindowDrawList(),
+        HalaImGuiDrawListType::Background => imgui::sys::igGetBackgroundDrawList(),
+        HalaImGuiDrawListType::Foreground => imgui::sys::igGetForegroundDrawList(),
+      };
+      imgui::sys::ImDrawList_AddCallback(
+        draw_list,
+        Some(hala_imgui_draw_callback),
+        callback_index as *mut std::ffi::c_void,
+      );
+    }
+  }
+
+  /// Set the gamepad input source for the navigation, it is polled at the beginning of each frame.
+  /// param source: The gamepad input source, e.g. HalaImGuiEvdevGamepad.
+  pub fn set_gamepad_source<T: HalaImGuiGamepadSource + 'static>(&mut self, source: T) {
+    self.gamepad.set_source(Some(Box::new(source)));
+  }
+
+  /// Remove the gamepad input source.
+  pub fn remove_gamepad_source(&mut self) {
+    self.gamepad.set_source(None);
+  }
+
+  /// Set the deadzones of the gamepad, the values inside are ignored and the rest are remapped to 0.0 to 1.0.
+  /// param stick_deadzone: The deadzone of the sticks, from 0.0 to 1.0.
+  /// param trigger_deadzone: The deadzone of the triggers, from 0.0 to 1.0.
+  pub fn set_gamepad_deadzones(&mut self, stick_deadzone: f32, trigger_deadzone: f32) {
+    self.gamepad.stick_deadzone = stick_deadzone.clamp(0.0, 1.0);
+    self.gamepad.trigger_deadzone = trigger_deadzone.clamp(0.0, 1.0);
+  }
+
+  /// Get the IME data of the text input in the last ended frame, to place the IME candidate window.
+  /// return: The IME data, None if no text input wants the IME.
+  pub fn get_ime_data(&self) -> Option<HalaImGuiImeData> {
+    self.renderer_data.ime_data.get()
+  }
+
+  /// Set the text being composed by the IME, it is rendered inline at the text caret.
+  /// param text: The preedit text, an empty text ends the composition.
+  /// param cursor: The byte range of the IME cursor in the text, None to hide the cursor.
+  pub fn set_ime_preedit(&mut self, text: &str, cursor: Option<(usize, usize)>) {
+    // The preedit text is rendered before it is committed, so the on demand fonts need its glyphs too.
+    self.renderer_data.requested_glyphs.borrow_mut().extend(text.chars().map(|c| c as u32));
+    self.ime_preedit.set(text, cursor);
+  }
+
+  /// Clear the text being composed by the IME.
+  pub fn clear_ime_preedit(&mut self) {
+    self.ime_preedit.clear();
+  }
+
+  /// Whether the IME is composing, the key events belong to the IME then.
+  /// return: Whether the IME is composing.
+  pub fn is_ime_composing(&self) -> bool {
+    self.ime_preedit.is_composing()
+  }
+
+  /// Get the mouse cursor requested by the last frame.
+  /// return: The mouse cursor, None if ImGUI wants the cursor hidden.
+  pub fn get_mouse_cursor(&self) -> Option<imgui::MouseCursor> {
+    self.mouse_cursor
+  }
+
+  /// Get the OS cursor icon which the window should show after the last frame.
+  /// The OS cursor is hidden when ImGUI wants no cursor, or when ImGUI draws the software cursor itself.
+  /// return: None if the application owns the cursor, Some(None) to hide the OS cursor, or Some(Some(icon)) to show the icon.
+  pub fn get_os_cursor_icon(&self) -> Option<Option<CursorIcon>> {
+    let io = self.imgui.io();
+    if io.config_flags.contains(imgui::ConfigFlags::NO_MOUSE_CURSOR_CHANGE) {
+      return None;
+    }
+    if io.mouse_draw_cursor {
+      return Some(None);
+    }
+    Some(self.mouse_cursor.map(Self::to_cursor_icon))
+  }
+
+  /// Set whether ImGUI draws the software mouse cursor, e.g. for the capture or the platforms without the OS cursor.
+  /// param draw_cursor: Whether to draw the software cursor.
+  pub fn set_mouse_draw_cursor(&mut self, draw_cursor: bool) {
+    self.imgui.io_mut().mouse_draw_cursor = draw_cursor;
+  }
+
+  /// Get whether ImGUI draws the software mouse cursor.
+  /// return: Whether to draw the software cursor.
+  pub fn get_mouse_draw_cursor(&self) -> bool {
+    self.imgui.io().mouse_draw_cursor
   }
 
   /// Whether any mouse button is down.
@@ -751,6 +2426,7 @@ impl HalaImGui {
   /// Add a input character.
   /// param char: The character.
   pub fn add_input_character(&mut self, char: u32) {
+    self.renderer_data.requested_glyphs.borrow_mut().insert(char);
     self.imgui.io_mut().add_input_character(char::from_u32(char).unwrap())
   }
 
@@ -784,66 +2460,1130 @@ impl HalaImGui {
     self.imgui.io_mut().add_mouse_wheel_event([h, v])
   }
 
+  /// Add a mouse source event, it tags the following mouse events.
+  /// The Dear ImGUI bundled with imgui-rs has no io.MouseSource yet, so the source is kept by the ImGUI context
+  /// for the widgets to query with HalaImGui::mouse_source inside the UI function.
+  /// param source: The mouse source.
+  pub fn add_mouse_source_event(&mut self, source: HalaImGuiMouseSource) {
+    self.renderer_data.mouse_source.set(source);
+  }
+
+  /// Get the source of the last mouse events.
+  /// return: The mouse source.
+  pub fn get_mouse_source(&self) -> HalaImGuiMouseSource {
+    self.renderer_data.mouse_source.get()
+  }
+
+  /// Get the source of the last mouse events inside the UI function, so the widgets can adapt to the touch input.
+  /// param ui: The UI of the current frame.
+  /// return: The mouse source, Mouse if the ImGUI context is not created by HalaImGui.
+  pub fn mouse_source(_ui: &imgui::Ui) -> HalaImGuiMouseSource {
+    unsafe {
+      let io = imgui::sys::igGetIO();
+      let renderer_data = (*io).BackendRendererUserData as *const HalaImGuiRendererData;
+      if renderer_data.is_null() {
+        return HalaImGuiMouseSource::Mouse;
+      }
+      (*renderer_data).mouse_source.get()
+    }
+  }
+
   /// Create the fonts texture.
   fn create_fonts_texture(&mut self) -> Result<()> {
-    let context = self.vk_ctx.borrow();
+    let vk_ctx = Rc::clone(&self.vk_ctx);
+    let context = vk_ctx.borrow();
 
     let font_texture = self.imgui.fonts().build_rgba32_texture();
-    let upload_size = font_texture.data.len();
-    let width = font_texture.width;
-    let height = font_texture.height;
-    let pixels = font_texture.data;
+    // The atlas is white with straight alpha coverage, so the premultiplied font texels are the coverage itself.
+    let premultiplied;
+    let pixels = if self.desc.blend_mode == HalaImGuiBlendMode::Premultiplied {
+      premultiplied = font_texture.data.chunks_exact(4)
+        .flat_map(|texel| {
+          let a = texel[3] as u32;
+          [(texel[0] as u32 * a / 255) as u8, (texel[1] as u32 * a / 255) as u8, (texel[2] as u32 * a / 255) as u8, texel[3]]
+        })
+        .collect::<Vec<u8>>();
+      premultiplied.as_slice()
+    } else {
+      font_texture.data
+    };
+    let font_image = Self::upload_image(
+      &context,
+      hala_gfx::HalaFormat::R8G8B8A8_UNORM,
+      font_texture.width,
+      font_texture.height,
+      pixels,
+      "imgui_font",
+    )?;
+
+    // Update descriptor set.
+    let mut texture = self.create_texture(&font_image, None, &HalaImGuiTextureOptions::default())?;
+    texture.image = Some(font_image);
+    if let Some(texture) = self.textures.insert(FONT_TEXTURE_ID, texture) {
+      self.retire(HalaImGuiRetiredResource::Texture(texture));
+    }
+    self.imgui.fonts().tex_id = imgui::TextureId::new(FONT_TEXTURE_ID);
+    self.fonts_dirty = false;
 
+    Ok(())
+  }
+
+  /// Create an image and upload the pixels to it through a staging buffer.
+  /// param context: The Vulkan context.
+  /// param format: The format of the image.
+  /// param width: The width of the image.
+  /// param height: The height of the image.
+  /// param pixels: The pixels.
+  /// param debug_name: The debug name.
+  /// return: The image.
+  fn upload_image(
+    context: &hala_gfx::HalaContext,
+    format: hala_gfx::HalaFormat,
+    width: u32,
+    height: u32,
+    pixels: &[u8],
+    debug_name: &str,
+  ) -> Result<hala_gfx::HalaImage> {
     // Create image.
-    let font_image = hala_gfx::HalaImage::new_2d(
+    let image = hala_gfx::HalaImage::new_2d(
       Rc::clone(&context.logical_device),
       hala_gfx::HalaImageUsageFlags::SAMPLED | hala_gfx::HalaImageUsageFlags::TRANSFER_DST,
-      hala_gfx::HalaFormat::R8G8B8A8_UNORM,
+      format,
       width,
       height,
       1,
       1,
       hala_gfx::HalaMemoryLocation::GpuOnly,
-      "imgui_font.image",
+      &format!("{}.image", debug_name),
     )?;
     let upload_buffer = hala_gfx::HalaBuffer::new(
       Rc::clone(&context.logical_device),
-      upload_size as u64,
+      pixels.len() as u64,
       hala_gfx::HalaBufferUsageFlags::TRANSFER_SRC,
       hala_gfx::HalaMemoryLocation::CpuToGpu,
-      "imgui_font_upload.buffer",
+      &format!("{}_upload.buffer", debug_name),
     )?;
 
-    let font_command_buffers = hala_gfx::HalaCommandBufferSet::new(
+    let upload_command_buffers = hala_gfx::HalaCommandBufferSet::new(
       Rc::clone(&context.logical_device),
       Rc::clone(&context.command_pools),
       hala_gfx::HalaCommandBufferType::GRAPHICS,
       hala_gfx::HalaCommandBufferLevel::PRIMARY,
       1,
-      "imgui_font.cmdbuf",
+      &format!("{}.cmdbuf", debug_name),
     )?;
-    font_image.update_gpu_memory_with_buffer(
+    image.update_gpu_memory_with_buffer(
       pixels,
       hala_gfx::HalaPipelineStageFlags2::FRAGMENT_SHADER
         | hala_gfx::HalaPipelineStageFlags2::TRANSFER,
       hala_gfx::HalaAccessFlags2::SHADER_READ,
       hala_gfx::HalaImageLayout::SHADER_READ_ONLY_OPTIMAL,
       &upload_buffer,
-      &font_command_buffers,
+      &upload_command_buffers,
     )?;
 
-    // Update descriptor set.
-    self.font_descriptor_set.update_combined_image_samplers(
-      0,
-      0,
+    Ok(image)
+  }
+
+  /// Load an image file and register it as a texture.
+  /// Call unregister_texture with the texture ID to release the image.
+  /// param path: The path of the image file.
+  /// return: The loaded image.
+  pub fn load_image_from_file<P: AsRef<Path>>(&mut self, path: P) -> Result<HalaImGuiImage> {
+    self.load_image_from_file_with_options(path, &HalaImGuiTextureOptions::default())
+  }
+
+  /// Load an image file and register it as a texture with the options.
+  /// Call unregister_texture with the texture ID to release the image.
+  /// param path: The path of the image file.
+  /// param options: The texture options.
+  /// return: The loaded image.
+  pub fn load_image_from_file_with_options<P: AsRef<Path>>(&mut self, path: P, options: &HalaImGuiTextureOptions) -> Result<HalaImGuiImage> {
+    let path = path.as_ref();
+    let image = image::ImageReader::open(path)?
+      .with_guessed_format()?
+      .decode()?;
+
+    let debug_name = format!("imgui_{}", path.file_stem().map(|s| s.to_string_lossy()).unwrap_or_default());
+    self.create_image_texture(image, &debug_name, options)
+  }
+
+  /// Load an image from the encoded bytes and register it as a texture.
+  /// Call unregister_texture with the texture ID to release the image.
+  /// param data: The encoded bytes, the format is guessed from the content.
+  /// param debug_name: The debug name.
+  /// return: The loaded image.
+  pub fn load_image_from_memory(&mut self, data: &[u8], debug_name: &str) -> Result<HalaImGuiImage> {
+    self.load_image_from_memory_with_options(data, debug_name, &HalaImGuiTextureOptions::default())
+  }
+
+  /// Load an image from the encoded bytes and register it as a texture with the options.
+  /// Call unregister_texture with the texture ID to release the image.
+  /// param data: The encoded bytes, the format is guessed from the content.
+  /// param debug_name: The debug name.
+  /// param options: The texture options.
+  /// return: The loaded image.
+  pub fn load_image_from_memory_with_options(&mut self, data: &[u8], debug_name: &str, options: &HalaImGuiTextureOptions) -> Result<HalaImGuiImage> {
+    let image = image::load_from_memory(data)?;
+
+    self.create_image_texture(image, debug_name, options)
+  }
+
+  /// Upload the decoded image and register it as a texture owned by the ImGUI context.
+  /// The 8-bit images are uploaded as R8G8B8A8_UNORM and the HDR images as R32G32B32A32_SFLOAT.
+  /// param image: The decoded image.
+  /// param debug_name: The debug name.
+  /// param options: The texture options.
+  /// return: The loaded image.
+  fn create_image_texture(&mut self, image: image::DynamicImage, debug_name: &str, options: &HalaImGuiTextureOptions) -> Result<HalaImGuiImage> {
+    let width = image.width();
+    let height = image.height();
+    let hala_image = {
+      let vk_ctx = Rc::clone(&self.vk_ctx);
+      let context = vk_ctx.borrow();
+
+      match image {
+        image::DynamicImage::ImageRgb32F(_) | image::DynamicImage::ImageRgba32F(_) => {
+          let rgba = image.into_rgba32f().into_raw();
+          let pixels = unsafe {
+            std::slice::from_raw_parts(rgba.as_ptr() as *const u8, std::mem::size_of_val(rgba.as_slice()))
+          };
+          Self::upload_image(&context, hala_gfx::HalaFormat::R32G32B32A32_SFLOAT, width, height, pixels, debug_name)?
+        },
+        _ => {
+          let pixels = image.into_rgba8().into_raw();
+          Self::upload_image(&context, hala_gfx::HalaFormat::R8G8B8A8_UNORM, width, height, &pixels, debug_name)?
+        },
+      }
+    };
+
+    let mut texture = self.create_texture(&hala_image, None, options)?;
+    texture.image = Some(hala_image);
+
+    let texture_id = self.next_texture_id;
+    self.next_texture_id += 1;
+    self.textures.insert(texture_id, texture);
+
+    log::debug!("ImGUI image \"{}\" {}x{} loaded as texture {}.", debug_name, width, height, texture_id);
+    Ok(HalaImGuiImage {
+      texture_id: imgui::TextureId::new(texture_id),
+      width,
+      height,
+    })
+  }
+
+  /// Register a user texture.
+  /// The image and the sampler must outlive the registration, call unregister_texture before dropping them.
+  /// param image: The image.
+  /// param sampler: The sampler.
+  /// return: The texture ID used by imgui::Ui::image and friends.
+  pub fn register_texture(&mut self, image: &hala_gfx::HalaImage, sampler: &hala_gfx::HalaSampler) -> Result<imgui::TextureId> {
+    let texture = self.create_texture(image, Some(sampler), &HalaImGuiTextureOptions::default())?;
+
+    let texture_id = self.next_texture_id;
+    self.next_texture_id += 1;
+    self.textures.insert(texture_id, texture);
+
+    log::debug!("ImGUI texture {} registered.", texture_id);
+    Ok(imgui::TextureId::new(texture_id))
+  }
+
+  /// Register a user texture with the sampler and the alpha mode of the options.
+  /// The image must outlive the registration, call unregister_texture before dropping it.
+  /// param image: The image.
+  /// param options: The texture options.
+  /// return: The texture ID used by imgui::Ui::image and friends.
+  pub fn register_texture_with_options(&mut self, image: &hala_gfx::HalaImage, options: &HalaImGuiTextureOptions) -> Result<imgui::TextureId> {
+    let texture = self.create_texture(image, None, options)?;
+
+    let texture_id = self.next_texture_id;
+    self.next_texture_id += 1;
+    self.textures.insert(texture_id, texture);
+
+    log::debug!("ImGUI texture {} registered with {:?}.", texture_id, options);
+    Ok(imgui::TextureId::new(texture_id))
+  }
+
+  /// Update a registered user texture with a new image and sampler.
+  /// It is useful when the render target behind the texture is recreated.
+  /// param texture_id: The texture ID.
+  /// param image: The image.
+  /// param sampler: The sampler.
+  /// return: The result.
+  pub fn update_texture(&mut self, texture_id: imgui::TextureId, image: &hala_gfx::HalaImage, sampler: &hala_gfx::HalaSampler) -> Result<()> {
+    self.replace_texture(texture_id, image, Some(sampler), &HalaImGuiTextureOptions::default())
+  }
+
+  /// Update a registered user texture with a new image and the options.
+  /// param texture_id: The texture ID.
+  /// param image: The image.
+  /// param options: The texture options.
+  /// return: The result.
+  pub fn update_texture_with_options(&mut self, texture_id: imgui::TextureId, image: &hala_gfx::HalaImage, options: &HalaImGuiTextureOptions) -> Result<()> {
+    self.replace_texture(texture_id, image, None, options)
+  }
+
+  /// Unregister a user texture.
+  /// param texture_id: The texture ID.
+  /// return: The result.
+  pub fn unregister_texture(&mut self, texture_id: imgui::TextureId) -> Result<()> {
+    let texture_id = texture_id.id();
+    if texture_id == FONT_TEXTURE_ID {
+      return Err(anyhow!("The ImGUI font texture can not be unregistered."));
+    }
+    let texture = self.textures.remove(&texture_id)
+      .ok_or_else(|| anyhow!("The ImGUI texture {} is not registered.", texture_id))?;
+    self.retire(HalaImGuiRetiredResource::Texture(texture));
+
+    log::debug!("ImGUI texture {} unregistered.", texture_id);
+    Ok(())
+  }
+
+  /// Replace the descriptor set of a registered user texture.
+  /// param texture_id: The texture ID.
+  /// param image: The image.
+  /// param sampler: The sampler, None to use the sampler of the options.
+  /// param options: The texture options.
+  /// return: The result.
+  fn replace_texture(
+    &mut self,
+    texture_id: imgui::TextureId,
+    image: &hala_gfx::HalaImage,
+    sampler: Option<&hala_gfx::HalaSampler>,
+    options: &HalaImGuiTextureOptions,
+  ) -> Result<()> {
+    let texture_id = texture_id.id();
+    if texture_id == FONT_TEXTURE_ID || !self.textures.contains_key(&texture_id) {
+      return Err(anyhow!("The ImGUI texture {} is not registered.", texture_id));
+    }
+
+    // The old descriptor set may still be used by the frames in flight, so we use a new one.
+    let texture = self.create_texture(image, sampler, options)?;
+    if let Some(texture) = self.textures.insert(texture_id, texture) {
+      self.retire(HalaImGuiRetiredResource::Texture(texture));
+    }
+
+    Ok(())
+  }
+
+  /// Create the texture with a new descriptor set.
+  /// param image: The image.
+  /// param sampler: The sampler, None to use the sampler of the options.
+  /// param options: The texture options.
+  /// return: The texture without the owned image.
+  fn create_texture(
+    &mut self,
+    image: &hala_gfx::HalaImage,
+    sampler: Option<&hala_gfx::HalaSampler>,
+    options: &HalaImGuiTextureOptions,
+  ) -> Result<HalaImGuiTexture> {
+    let vk_ctx = Rc::clone(&self.vk_ctx);
+    let context = vk_ctx.borrow();
+
+    let binding = match self.desc.bindless_textures {
+      Some(capacity) => HalaImGuiTextureBinding::BindlessSlot(self.allocate_bindless_slot(capacity)?),
+      None => HalaImGuiTextureBinding::DescriptorSet(self.allocate_texture_descriptor_set()?),
+    };
+    let sampler = match sampler {
+      Some(sampler) => sampler,
+      None => self.samplers.get_or_create(&context.logical_device, &options.sampler)?,
+    };
+    match &binding {
+      HalaImGuiTextureBinding::DescriptorSet(descriptor_set) => {
+        descriptor_set.update_combined_image_samplers(
+          0,
+          0,
+          &[
+            (image, sampler),
+          ],
+        );
+      },
+      HalaImGuiTextureBinding::BindlessSlot(slot) => {
+        // The slot is not used by any frame in flight, see reclaim.
+        self.bindless_descriptor_set.as_ref().unwrap().update_combined_image_samplers_at(
+          0,
+          0,
+          *slot,
+          &[
+            (image, sampler),
+          ],
+        );
+      },
+    }
+
+    Ok(HalaImGuiTexture {
+      binding,
+      image: None,
+      alpha_mode: options.alpha_mode.unwrap_or(self.desc.blend_mode),
+      flags: if LINEAR_TEXTURE_FORMATS.contains(&image.format) { TEXTURE_FLAG_LINEAR } else { 0 },
+    })
+  }
+
+  /// Create the graphics pipelines of all blend modes.
+  /// param context: The Vulkan context.
+  /// param desc: The description of the ImGUI context.
+  /// param descriptor_set_layout: The descriptor set layout of the textures.
+  /// param vert_shader: The vertex shader.
+  /// param frag_shaders: The fragment shaders indexed by the blend mode.
+  /// return: The graphics pipelines indexed by the blend mode.
+  fn create_pipelines(
+    context: &hala_gfx::HalaContext,
+    desc: &HalaImGuiDesc,
+    descriptor_set_layout: &hala_gfx::HalaDescriptorSetLayout,
+    vert_shader: &hala_gfx::HalaShader,
+    frag_shaders: &[hala_gfx::HalaShader],
+  ) -> Result<Vec<hala_gfx::HalaGraphicsPipeline>> {
+    BLEND_MODES
+      .into_iter()
+      .map(|blend_mode| Self::create_pipeline(
+        context,
+        desc,
+        blend_mode,
+        descriptor_set_layout,
+        vert_shader,
+        &frag_shaders[blend_mode as usize],
+      ))
+      .collect()
+  }
+
+  /// Create the graphics pipeline against the swapchain or the offscreen render target.
+  /// param context: The Vulkan context.
+  /// param desc: The description of the ImGUI context.
+  /// param blend_mode: The blend mode.
+  /// param descriptor_set_layout: The descriptor set layout of the textures.
+  /// param vert_shader: The vertex shader.
+  /// param frag_shader: The fragment shader.
+  /// return: The graphics pipeline.
+  fn create_pipeline(
+    context: &hala_gfx::HalaContext,
+    desc: &HalaImGuiDesc,
+    blend_mode: HalaImGuiBlendMode,
+    descriptor_set_layout: &hala_gfx::HalaDescriptorSetLayout,
+    vert_shader: &hala_gfx::HalaShader,
+    frag_shader: &hala_gfx::HalaShader,
+  ) -> Result<hala_gfx::HalaGraphicsPipeline> {
+    let (color_format, depth_format) = match desc.color_format {
+      Some(color_format) => (color_format, desc.depth_format),
+      None => (context.swapchain.format, Some(desc.depth_format.unwrap_or(context.swapchain.depth_stencil_format))),
+    };
+    // The fragment shader of the premultiplied alpha pipeline premultiplies the vertex colors, see specialize_premultiply_color.
+    let color_blend = match blend_mode {
+      HalaImGuiBlendMode::Straight => hala_gfx::HalaBlendState::new(hala_gfx::HalaBlendFactor::SRC_ALPHA, hala_gfx::HalaBlendFactor::ONE_MINUS_SRC_ALPHA, hala_gfx::HalaBlendOp::ADD),
+      HalaImGuiBlendMode::Premultiplied => hala_gfx::HalaBlendState::new(hala_gfx::HalaBlendFactor::ONE, hala_gfx::HalaBlendFactor::ONE_MINUS_SRC_ALPHA, hala_gfx::HalaBlendOp::ADD),
+    };
+    let depth_state = match desc.depth_test {
+      Some(compare_op) => hala_gfx::HalaDepthState::new(true, desc.depth_write, compare_op),
+      None => hala_gfx::HalaDepthState::new(false, false, hala_gfx::HalaCompareOp::NEVER),
+    };
+    // HalaImGui::draw_to_image does not resolve, so the offscreen render target is single sample by default.
+    let sample_count = desc.sample_count.unwrap_or(if desc.color_format.is_some() {
+      hala_gfx::HalaSampleCountFlags::TYPE_1
+    } else {
+      context.multisample_count
+    });
+
+    // The viewport and the scissor are dynamic, so the size is only nominal.
+    let pipeline = hala_gfx::HalaGraphicsPipeline::with_format_and_size(
+      Rc::clone(&context.logical_device),
+      &[color_format],
+      depth_format,
+      context.swapchain.dims.width,
+      context.swapchain.dims.height,
+      &[descriptor_set_layout],
+      hala_gfx::HalaPipelineCreateFlags::default(),
+      &[
+        // Position.
+        hala_gfx::HalaVertexInputAttributeDescription {
+          binding: 0,
+          location: 0,
+          offset: 0,
+          format: hala_gfx::HalaFormat::R32G32_SFLOAT,
+        },
+        // UV.
+        hala_gfx::HalaVertexInputAttributeDescription {
+          binding: 0,
+          location: 1,
+          offset: 8,
+          format: hala_gfx::HalaFormat::R32G32_SFLOAT,
+        },
+        // Color.
+        hala_gfx::HalaVertexInputAttributeDescription {
+          binding: 0,
+          location: 2,
+          offset: 16,
+          format: hala_gfx::HalaFormat::R8G8B8A8_UNORM,
+        },
+      ],
+      &[
+        hala_gfx::HalaVertexInputBindingDescription {
+          binding: 0,
+          stride: 20,
+          input_rate: hala_gfx::HalaVertexInputRate::VERTEX,
+        }
+      ],
+      &[
+        hala_gfx::HalaPushConstantRange {
+          stage_flags: hala_gfx::HalaShaderStageFlags::VERTEX,
+          offset: 0,
+          size: 16,
+        },
+        // The paper white of the HDR output modes, the bindless texture index and the texture flags.
+        hala_gfx::HalaPushConstantRange {
+          stage_flags: hala_gfx::HalaShaderStageFlags::FRAGMENT,
+          offset: 16,
+          size: 12,
+        },
+      ],
+      hala_gfx::HalaPrimitiveTopology::TRIANGLE_LIST,
+      &color_blend,
+      &hala_gfx::HalaBlendState::new(hala_gfx::HalaBlendFactor::ONE, hala_gfx::HalaBlendFactor::ONE_MINUS_SRC_ALPHA, hala_gfx::HalaBlendOp::ADD),
+      &hala_gfx::HalaRasterizerState::new(hala_gfx::HalaFrontFace::COUNTER_CLOCKWISE, hala_gfx::HalaCullModeFlags::NONE, hala_gfx::HalaPolygonMode::FILL, 1.0),
+      &hala_gfx::HalaMultisampleState::new(sample_count, false, 0.0, &[], false, false),
+      &depth_state,
+      None,
+      &[vert_shader, frag_shader],
+      &[hala_gfx::HalaDynamicState::VIEWPORT, hala_gfx::HalaDynamicState::SCISSOR],
+      None,
+      match blend_mode {
+        HalaImGuiBlendMode::Straight => "imgui.pipeline",
+        HalaImGuiBlendMode::Premultiplied => "imgui_premultiplied.pipeline",
+      },
+    )?;
+
+    Ok(pipeline)
+  }
+
+  /// Resolve the output mode for the color format.
+  /// param output_mode: The output mode of the description.
+  /// param color_format: The color format of the render target.
+  /// param is_swapchain: Whether the render target is the swapchain.
+  /// return: The output mode except Auto.
+  fn resolve_output_mode(output_mode: HalaImGuiOutputMode, color_format: hala_gfx::HalaFormat, is_swapchain: bool) -> HalaImGuiOutputMode {
+    if output_mode != HalaImGuiOutputMode::Auto {
+      return output_mode;
+    }
+
+    // The FP16 swapchain is presented in the extended sRGB linear color space.
+    // The HDR10 swapchain shares the formats with SDR, so it must be selected explicitly.
+    if is_swapchain && color_format == hala_gfx::HalaFormat::R16G16B16A16_SFLOAT {
+      return HalaImGuiOutputMode::Scrgb;
+    }
+
+    // The sRGB formats encode the linear colors written by the fragment shader.
+    let srgb_formats = [
+      hala_gfx::HalaFormat::R8G8B8A8_SRGB,
+      hala_gfx::HalaFormat::B8G8R8A8_SRGB,
+      hala_gfx::HalaFormat::A8B8G8R8_SRGB_PACK32,
+    ];
+    if srgb_formats.contains(&color_format) {
+      HalaImGuiOutputMode::Linear
+    } else {
+      HalaImGuiOutputMode::Gamma
+    }
+  }
+
+  /// Create the vertex shader.
+  /// param logical_device: The logical device.
+  /// param desc: The description.
+  /// return: The vertex shader.
+  fn create_vert_shader(
+    logical_device: &Rc<RefCell<hala_gfx::HalaLogicalDevice>>,
+    desc: &HalaImGuiDesc,
+  ) -> Result<hala_gfx::HalaShader> {
+    let (spv, debug_name): (&[u32], _) = match &desc.vert_spv {
+      Some(spv) => (spv, "imgui_custom.vert.spv"),
+      None => (&__GLSL_SHADER_VERT_SPV, "imgui.vert.spv"),
+    };
+    let vert_code = unsafe {
+      std::slice::from_raw_parts(spv.as_ptr() as *const u8, std::mem::size_of_val(spv))
+    };
+    let vert_shader = hala_gfx::HalaShader::new(
+      Rc::clone(logical_device),
+      vert_code,
+      hala_gfx::HalaShaderStageFlags::VERTEX,
+      hala_gfx::HalaRayTracingShaderGroupType::GENERAL,
+      debug_name,
+    )?;
+
+    Ok(vert_shader)
+  }
+
+  /// Create the fragment shader for the output mode.
+  /// param logical_device: The logical device.
+  /// param desc: The description.
+  /// param output_mode: The resolved output mode.
+  /// param blend_mode: The blend mode of the pipeline using the shader.
+  /// return: The fragment shader.
+  fn create_frag_shader(
+    logical_device: &Rc<RefCell<hala_gfx::HalaLogicalDevice>>,
+    desc: &HalaImGuiDesc,
+    output_mode: HalaImGuiOutputMode,
+    blend_mode: HalaImGuiBlendMode,
+  ) -> Result<hala_gfx::HalaShader> {
+    let bindless = desc.bindless_textures.is_some();
+    let (spv, debug_name): (&[u32], _) = match (&desc.frag_spv, output_mode, bindless) {
+      (Some(spv), _, _) => (spv, "imgui_custom.frag.spv"),
+      (None, HalaImGuiOutputMode::Linear, false) => (&__GLSL_SHADER_FRAG_LINEAR_SPV, "imgui_linear.frag.spv"),
+      (None, HalaImGuiOutputMode::Scrgb, false) => (&__GLSL_SHADER_FRAG_SCRGB_SPV, "imgui_scrgb.frag.spv"),
+      (None, HalaImGuiOutputMode::Hdr10, false) => (&__GLSL_SHADER_FRAG_HDR10_SPV, "imgui_hdr10.frag.spv"),
+      (None, _, false) => (&__GLSL_SHADER_FRAG_SPV, "imgui.frag.spv"),
+      (None, HalaImGuiOutputMode::Linear, true) => (&__GLSL_SHADER_FRAG_BINDLESS_LINEAR_SPV, "imgui_bindless_linear.frag.spv"),
+      (None, HalaImGuiOutputMode::Scrgb, true) => (&__GLSL_SHADER_FRAG_BINDLESS_SCRGB_SPV, "imgui_bindless_scrgb.frag.spv"),
+      (None, HalaImGuiOutputMode::Hdr10, true) => (&__GLSL_SHADER_FRAG_BINDLESS_HDR10_SPV, "imgui_bindless_hdr10.frag.spv"),
+      (None, _, true) => (&__GLSL_SHADER_FRAG_BINDLESS_SPV, "imgui_bindless.frag.spv"),
+    };
+    let spv = Self::specialize_premultiply_color(spv, blend_mode == HalaImGuiBlendMode::Premultiplied);
+    let frag_code = unsafe {
+      std::slice::from_raw_parts(spv.as_ptr() as *const u8, std::mem::size_of_val(spv.as_slice()))
+    };
+    let frag_shader = hala_gfx::HalaShader::new(
+      Rc::clone(logical_device),
+      frag_code,
+      hala_gfx::HalaShaderStageFlags::FRAGMENT,
+      hala_gfx::HalaRayTracingShaderGroupType::GENERAL,
+      debug_name,
+    )?;
+
+    Ok(frag_shader)
+  }
+
+  /// Create the fragment shaders of all blend modes.
+  /// param logical_device: The logical device.
+  /// param desc: The description.
+  /// param output_mode: The resolved output mode.
+  /// return: The fragment shaders indexed by the blend mode.
+  fn create_frag_shaders(
+    logical_device: &Rc<RefCell<hala_gfx::HalaLogicalDevice>>,
+    desc: &HalaImGuiDesc,
+    output_mode: HalaImGuiOutputMode,
+  ) -> Result<Vec<hala_gfx::HalaShader>> {
+    BLEND_MODES
+      .into_iter()
+      .map(|blend_mode| Self::create_frag_shader(logical_device, desc, output_mode, blend_mode))
+      .collect()
+  }
+
+  /// Set the default value of the PREMULTIPLY_COLOR specialization constant in the SPIR-V.
+  /// The pipelines are created without the specialization info, so the default value is what the shader sees.
+  /// The SPIR-V without the constant is returned as is.
+  /// param spv: The SPIR-V of the fragment shader.
+  /// param premultiply: Whether to premultiply the vertex colors by their alpha.
+  /// return: The specialized SPIR-V.
+  fn specialize_premultiply_color(spv: &[u32], premultiply: bool) -> Vec<u32> {
+    const OP_DECORATE: u32 = 71;
+    const OP_SPEC_CONSTANT_TRUE: u32 = 48;
+    const OP_SPEC_CONSTANT_FALSE: u32 = 49;
+    const DECORATION_SPEC_ID: u32 = 1;
+    // The instructions start after the 5 words of the header.
+    const HEADER_SIZE: usize = 5;
+
+    // The word count is in the high half of the first word of an instruction, the opcode in the low half.
+    let mut offsets = Vec::new();
+    let mut offset = HEADER_SIZE;
+    while offset < spv.len() {
+      let word_count = (spv[offset] >> 16) as usize;
+      if word_count == 0 || offset + word_count > spv.len() {
+        break;
+      }
+      offsets.push(offset);
+      offset += word_count;
+    }
+
+    let mut spv = spv.to_vec();
+    let ids = offsets.iter()
+      .filter(|&&offset| spv[offset] & 0xFFFF == OP_DECORATE && spv[offset] >> 16 == 4)
+      .filter(|&&offset| spv[offset + 2] == DECORATION_SPEC_ID && spv[offset + 3] == PREMULTIPLY_COLOR_SPEC_ID)
+      .map(|&offset| spv[offset + 1])
+      .collect::<Vec<_>>();
+    for offset in offsets {
+      let opcode = spv[offset] & 0xFFFF;
+      if (opcode == OP_SPEC_CONSTANT_TRUE || opcode == OP_SPEC_CONSTANT_FALSE) && ids.contains(&spv[offset + 2]) {
+        let opcode = if premultiply { OP_SPEC_CONSTANT_TRUE } else { OP_SPEC_CONSTANT_FALSE };
+        spv[offset] = (spv[offset] & 0xFFFF0000) | opcode;
+      }
+    }
+
+    spv
+  }
+
+  /// Get the index type matching the ImGUI draw index.
+  /// return: UINT32 if ImGUI is built with 32-bit draw indices, otherwise UINT16.
+  const fn index_type() -> hala_gfx::HalaIndexType {
+    Self::index_type_of_size(std::mem::size_of::<imgui::DrawIdx>())
+  }
+
+  /// Get the index type of the draw index size.
+  /// param size: The size of the draw index in bytes.
+  /// return: UINT32 for 4 bytes, otherwise UINT16.
+  const fn index_type_of_size(size: usize) -> hala_gfx::HalaIndexType {
+    if size == 4 {
+      hala_gfx::HalaIndexType::UINT32
+    } else {
+      hala_gfx::HalaIndexType::UINT16
+    }
+  }
+
+  /// Create the per-frame vertex/index buffers.
+  /// param frames_in_flight: The number of frames in flight.
+  /// return: The vertex buffers and the index buffers.
+  fn create_frame_buffers(frames_in_flight: usize) -> (Vec<HalaImGuiBuffer>, Vec<HalaImGuiBuffer>) {
+    let vertex_buffers = (0..frames_in_flight)
+      .map(|i| HalaImGuiBuffer::new(hala_gfx::HalaBufferUsageFlags::VERTEX_BUFFER, &format!("imgui_vertex_{}.buffer", i)))
+      .collect();
+    let index_buffers = (0..frames_in_flight)
+      .map(|i| HalaImGuiBuffer::new(hala_gfx::HalaBufferUsageFlags::INDEX_BUFFER, &format!("imgui_index_{}.buffer", i)))
+      .collect();
+
+    (vertex_buffers, index_buffers)
+  }
+
+  /// Check the descriptor indexing features and the descriptor limits used by the bindless texture array.
+  /// param context: The Vulkan context.
+  /// param capacity: The capacity of the bindless texture array.
+  /// return: The result, error with the first missing feature or exceeded limit.
+  fn check_bindless_features(context: &hala_gfx::HalaContext, capacity: usize) -> Result<()> {
+    let features = &context.physical_device.descriptor_indexing_features;
+    let required_features = [
+      ("runtimeDescriptorArray", features.runtime_descriptor_array),
+      ("descriptorBindingPartiallyBound", features.descriptor_binding_partially_bound),
+      ("descriptorBindingUpdateUnusedWhilePending", features.descriptor_binding_update_unused_while_pending),
+      ("shaderSampledImageArrayNonUniformIndexing", features.shader_sampled_image_array_non_uniform_indexing),
+    ];
+    if let Some((name, _)) = required_features.iter().find(|(_, supported)| *supported == 0) {
+      return Err(HalaImGuiError::UnsupportedBindlessTextures(format!("the device lacks the descriptor indexing feature {}", name)).into());
+    }
+
+    // The combined image samplers count against the sampler limits, and the update after bind limits are never lower.
+    let limits = &context.physical_device.properties.limits;
+    let descriptor_indexing_properties = &context.physical_device.descriptor_indexing_properties;
+    let descriptor_limits = [
+      ("maxPerStageDescriptorSamplers", limits.max_per_stage_descriptor_samplers),
+      ("maxDescriptorSetSamplers", limits.max_descriptor_set_samplers),
+      (
+        "maxPerStageDescriptorUpdateAfterBindSamplers",
+        descriptor_indexing_properties.max_per_stage_descriptor_update_after_bind_samplers,
+      ),
+      (
+        "maxDescriptorSetUpdateAfterBindSamplers",
+        descriptor_indexing_properties.max_descriptor_set_update_after_bind_samplers,
+      ),
+    ];
+    if let Some((name, limit)) = descriptor_limits.iter().find(|(_, limit)| capacity > *limit as usize) {
+      return Err(HalaImGuiError::UnsupportedBindlessTextures(
+        format!("the capacity {} exceeds {} {} of the device", capacity, name, limit)
+      ).into());
+    }
+
+    Ok(())
+  }
+
+  /// Create the descriptor set layout for the textures.
+  /// param logical_device: The logical device.
+  /// param bindless_textures: The capacity of the bindless texture array, None for one texture per descriptor set.
+  /// param debug_name: The debug name.
+  /// return: The descriptor set layout.
+  fn create_texture_descriptor_set_layout(
+    logical_device: &Rc<RefCell<hala_gfx::HalaLogicalDevice>>,
+    bindless_textures: Option<usize>,
+    debug_name: &str,
+  ) -> Result<hala_gfx::HalaDescriptorSetLayout> {
+    // The elements of the bindless texture array are written while the frames in flight use the others.
+    let (descriptor_count, binding_flags) = match bindless_textures {
+      Some(capacity) => (
+        capacity as u32,
+        hala_gfx::HalaDescriptorBindingFlags::PARTIALLY_BOUND | hala_gfx::HalaDescriptorBindingFlags::UPDATE_UNUSED_WHILE_PENDING,
+      ),
+      None => (1, hala_gfx::HalaDescriptorBindingFlags::PARTIALLY_BOUND),
+    };
+    let layout = hala_gfx::HalaDescriptorSetLayout::new(
+      Rc::clone(logical_device),
+      &[
+        hala_gfx::HalaDescriptorSetLayoutBinding {
+          binding_index: 0,
+          descriptor_type: hala_gfx::HalaDescriptorType::COMBINED_IMAGE_SAMPLER,
+          descriptor_count,
+          stage_flags: hala_gfx::HalaShaderStageFlags::FRAGMENT,
+          binding_flags,
+        },
+      ],
+      debug_name,
+    )?;
+
+    Ok(layout)
+  }
+
+  /// Create a descriptor pool for the textures.
+  /// param logical_device: The logical device.
+  /// param capacity: The number of descriptor sets in the pool.
+  /// param textures_per_set: The number of textures in a descriptor set.
+  /// param pool_index: The index of the pool.
+  /// return: The descriptor pool.
+  fn create_texture_descriptor_pool(
+    logical_device: &Rc<RefCell<hala_gfx::HalaLogicalDevice>>,
+    capacity: usize,
+    textures_per_set: usize,
+    pool_index: usize,
+  ) -> Result<Rc<RefCell<hala_gfx::HalaDescriptorPool>>> {
+    let pool = hala_gfx::HalaDescriptorPool::new(
+      Rc::clone(logical_device),
       &[
-        (&font_image, &self.font_sampler),
+        (hala_gfx::HalaDescriptorType::COMBINED_IMAGE_SAMPLER, capacity * textures_per_set),
       ],
+      capacity,
+      &format!("imgui_texture_{}.descpool", pool_index),
+    )?;
+
+    Ok(Rc::new(RefCell::new(pool)))
+  }
+
+  /// Allocate a descriptor set for a texture.
+  /// Reuse a reclaimed descriptor set if any, otherwise allocate from the texture descriptor pools.
+  /// return: The descriptor set.
+  fn allocate_texture_descriptor_set(&mut self) -> Result<hala_gfx::HalaDescriptorSet> {
+    if let Some(descriptor_set) = self.free_descriptor_sets.pop() {
+      return Ok(descriptor_set);
+    }
+
+    let vk_ctx = Rc::clone(&self.vk_ctx);
+    let context = vk_ctx.borrow();
+
+    // Grow the pools when the current one is full.
+    if self.texture_descriptor_pool_used >= self.texture_descriptor_pool_capacity {
+      self.texture_descriptor_pool_capacity *= 2;
+      self.texture_descriptor_pool_used = 0;
+      self.texture_descriptor_pools.push(Self::create_texture_descriptor_pool(
+        &context.logical_device,
+        self.texture_descriptor_pool_capacity,
+        1,
+        self.texture_descriptor_pools.len(),
+      )?);
+      log::debug!("ImGUI texture descriptor pool grown to {} descriptor sets.", self.texture_descriptor_pool_capacity);
+    }
+
+    // The descriptor set takes the ownership of its layout, the identically defined layouts are compatible
+    // with the pipeline layout.
+    let pool_index = self.texture_descriptor_pools.len() - 1;
+    let descriptor_set = hala_gfx::HalaDescriptorSet::new_static(
+      Rc::clone(&context.logical_device),
+      Rc::clone(&self.texture_descriptor_pools[pool_index]),
+      Self::create_texture_descriptor_set_layout(
+        &context.logical_device,
+        None,
+        &format!("imgui_texture_{}_{}.descsetlayout", pool_index, self.texture_descriptor_pool_used),
+      )?,
+      0,
+      &format!("imgui_texture_{}_{}.descset", pool_index, self.texture_descriptor_pool_used),
+    )?;
+    self.texture_descriptor_pool_used += 1;
+
+    Ok(descriptor_set)
+  }
+
+  /// Allocate an element of the bindless texture array.
+  /// param capacity: The capacity of the bindless texture array.
+  /// return: The index of the element.
+  fn allocate_bindless_slot(&mut self, capacity: usize) -> Result<u32> {
+    if let Some(slot) = self.free_bindless_slots.pop() {
+      return Ok(slot);
+    }
+    if self.next_bindless_slot as usize >= capacity {
+      return Err(anyhow!("The ImGUI bindless texture array is full with {} textures.", capacity));
+    }
+
+    let slot = self.next_bindless_slot;
+    self.next_bindless_slot += 1;
+    Ok(slot)
+  }
+
+  /// Retire a resource which may still be used by the frames in flight.
+  /// param resource: The resource.
+  fn retire(&mut self, resource: HalaImGuiRetiredResource) {
+    self.retired_resources.push_back((self.frame_serial, resource));
+  }
+
+  /// Reclaim the retired resources which are no longer used by any frame in flight.
+  /// The descriptor sets of the textures are moved to the free list, and the others are destroyed.
+  fn reclaim(&mut self) {
+    while let Some((retired_serial, _)) = self.retired_resources.front() {
+      if *retired_serial > self.completed_serial {
+        break;
+      }
+      let (_, resource) = self.retired_resources.pop_front().unwrap();
+      if let HalaImGuiRetiredResource::Texture(texture) = resource {
+        match texture.binding {
+          HalaImGuiTextureBinding::DescriptorSet(descriptor_set) => self.free_descriptor_sets.push(descriptor_set),
+          HalaImGuiTextureBinding::BindlessSlot(slot) => self.free_bindless_slots.push(slot),
+        }
+      }
+    }
+  }
+
+  /// Add a font from a TTF/OTF file.
+  /// param path: The path of the font file.
+  /// param size_pixels: The size of the font in logical pixels.
+  /// param glyph_ranges: The glyph ranges.
+  /// return: The font index used by get_font, merge_font_* and set_default_font.
+  pub fn add_font_from_file<P: AsRef<Path>>(&mut self, path: P, size_pixels: f32, glyph_ranges: HalaImGuiGlyphRanges) -> Result<usize> {
+    let source = HalaImGuiFontSource::with_file(path, size_pixels, glyph_ranges)?;
+
+    Ok(self.add_font(source))
+  }
+
+  /// Add a font from the TTF/OTF data.
+  /// param data: The TTF/OTF data.
+  /// param size_pixels: The size of the font in logical pixels.
+  /// param glyph_ranges: The glyph ranges.
+  /// return: The font index used by get_font, merge_font_* and set_default_font.
+  pub fn add_font_from_memory(&mut self, data: &[u8], size_pixels: f32, glyph_ranges: HalaImGuiGlyphRanges) -> usize {
+    let source = HalaImGuiFontSource::with_memory(data, size_pixels, glyph_ranges);
+
+    self.add_font(source)
+  }
+
+  /// Merge the glyphs of a TTF/OTF file into a font, e.g. an icon font.
+  /// param font: The font index.
+  /// param path: The path of the font file.
+  /// param size_pixels: The size of the font in logical pixels.
+  /// param glyph_ranges: The glyph ranges.
+  /// return: The result.
+  pub fn merge_font_from_file<P: AsRef<Path>>(&mut self, font: usize, path: P, size_pixels: f32, glyph_ranges: HalaImGuiGlyphRanges) -> Result<()> {
+    let source = HalaImGuiFontSource::with_file(path, size_pixels, glyph_ranges)?;
+
+    self.merge_font(font, source)
+  }
+
+  /// Merge the glyphs of the TTF/OTF data into a font, e.g. an icon font.
+  /// param font: The font index.
+  /// param data: The TTF/OTF data.
+  /// param size_pixels: The size of the font in logical pixels.
+  /// param glyph_ranges: The glyph ranges.
+  /// return: The result.
+  pub fn merge_font_from_memory(&mut self, font: usize, data: &[u8], size_pixels: f32, glyph_ranges: HalaImGuiGlyphRanges) -> Result<()> {
+    let source = HalaImGuiFontSource::with_memory(data, size_pixels, glyph_ranges);
+
+    self.merge_font(font, source)
+  }
+
+  /// Set the default font.
+  /// param font: The font index.
+  /// return: The result.
+  pub fn set_default_font(&mut self, font: usize) -> Result<()> {
+    if font >= self.fonts.len() {
+      return Err(anyhow!("The ImGUI font {} does not exist.", font));
+    }
+
+    self.default_font = font;
+    self.rebuild_fonts();
+
+    Ok(())
+  }
+
+  /// Get the ImGUI font ID for imgui::Ui::push_font.
+  /// The font ID is invalidated when the fonts change, so get it again after adding or merging fonts.
+  /// param font: The font index.
+  /// return: The font ID.
+  pub fn get_font(&self, font: usize) -> Option<imgui::FontId> {
+    self.font_ids.get(font).copied()
+  }
+
+  /// Track the characters of the text for the on demand fonts.
+  /// ImGUI does not report the text it renders, so the on demand fonts only see the typed, pasted and IME preedit
+  /// characters and the text passed through this function: wrap every label which may contain non-Latin-1 characters.
+  /// The missing glyphs of the frame are added to the font atlas in one rebuild after end_frame,
+  /// and the text renders with them from the next frame.
+  /// param ui: The UI of the current frame.
+  /// param text: The text which will be rendered.
+  /// return: The same text, so the call can wrap the text passed to the widgets.
+  pub fn track_text<'a>(_ui: &imgui::Ui, text: &'a str) -> &'a str {
+    request_glyphs(text);
+
+    text
+  }
+
+  /// Add the requested characters to the on demand fonts and rebuild the fonts if any glyph is missing.
+  /// It runs once per frame after the frame is rendered, so the rebuild and upload are batched.
+  fn update_on_demand_glyphs(&mut self) {
+    let requested_glyphs = std::mem::take(&mut *self.renderer_data.requested_glyphs.borrow_mut());
+    if !self.fonts.iter().any(|font| font.sources.iter().any(|source| source.is_on_demand())) {
+      return;
+    }
+
+    let num_of_glyphs = self.on_demand_glyphs.len();
+    self.on_demand_glyphs.extend(
+      requested_glyphs.into_iter().filter(|&c| c > 0xFF && char::from_u32(c).is_some_and(|c| !c.is_control()))
     );
+    if self.on_demand_glyphs.len() > num_of_glyphs {
+      log::debug!("ImGUI on demand glyphs grown from {} to {}.", num_of_glyphs, self.on_demand_glyphs.len());
+      self.rebuild_fonts();
+    }
+  }
 
-    self.font_image = Some(font_image);
+  /// Add a font.
+  /// param source: The source of the font.
+  /// return: The font index.
+  fn add_font(&mut self, source: HalaImGuiFontSource) -> usize {
+    self.fonts.push(HalaImGuiFont {
+      sources: vec![source],
+    });
+    self.rebuild_fonts();
+
+    self.fonts.len() - 1
+  }
+
+  /// Merge a font source into a font.
+  /// param font: The font index.
+  /// param source: The source of the font.
+  /// return: The result.
+  fn merge_font(&mut self, font: usize, source: HalaImGuiFontSource) -> Result<()> {
+    self.fonts.get_mut(font)
+      .ok_or_else(|| anyhow!("The ImGUI font {} does not exist.", font))?
+      .sources
+      .push(source);
+    self.rebuild_fonts();
 
     Ok(())
   }
 
-}
\ No newline at end of file
+  /// Rebuild the fonts at the current scale factor.
+  /// The fonts are rasterized in physical pixels and scaled back to logical pixels by the global font scale.
+  /// The font texture is uploaded again at the beginning of the next frame.
+  fn rebuild_fonts(&mut self) {
+    let scale_factor = self.scale_factor as f32;
+    let atlas = self.imgui.fonts();
+    atlas.clear();
+    for font in self.fonts.iter_mut() {
+      for source in font.sources.iter_mut() {
+        source.set_on_demand_glyphs(&self.on_demand_glyphs);
+      }
+    }
+
+    // ImGUI uses the first font as the default one.
+    let mut font_ids = Vec::with_capacity(self.fonts.len());
+    let order = std::iter::once(self.default_font)
+      .chain((0..self.fonts.len()).filter(|&i| i != self.default_font));
+    for i in order {
+      let sources = self.fonts[i].sources.iter()
+        .map(|source| source.to_imgui(scale_factor))
+        .collect::<Vec<_>>();
+      font_ids.push((i, atlas.add_font(&sources)));
+    }
+    font_ids.sort_by_key(|(i, _)| *i);
+    self.font_ids = font_ids.into_iter().map(|(_, font_id)| font_id).collect();
+
+    self.imgui.io_mut().font_global_scale = 1.0 / scale_factor;
+    self.fonts_dirty = true;
+  }
+
+}
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  fn new_batch(texture_id: usize, first_index: u32, index_count: u32, vertex_offset: i32) -> HalaImGuiDrawBatch {
+    HalaImGuiDrawBatch {
+      texture_id,
+      clip_rect: [0.0, 0.0, 100.0, 100.0],
+      first_index,
+      index_count,
+      vertex_offset,
+    }
+  }
+
+  /// Get the opcodes of the spec constants with the PREMULTIPLY_COLOR ID in the SPIR-V.
+  fn premultiply_color_opcodes(spv: &[u32]) -> Vec<u32> {
+    let mut offset = 5;
+    let mut ids = Vec::new();
+    let mut opcodes = Vec::new();
+    while offset < spv.len() {
+      let (word_count, opcode) = ((spv[offset] >> 16) as usize, spv[offset] & 0xFFFF);
+      if opcode == 71 && spv[offset + 2] == 1 && spv[offset + 3] == PREMULTIPLY_COLOR_SPEC_ID {
+        ids.push(spv[offset + 1]);
+      }
+      if (opcode == 48 || opcode == 49) && ids.contains(&spv[offset + 2]) {
+        opcodes.push(opcode);
+      }
+      offset += word_count;
+    }
+    opcodes
+  }
+
+  #[test]
+  fn built_in_frag_shaders_premultiply_by_blend_mode() {
+    let shaders: [&[u32]; 8] = [
+      &__GLSL_SHADER_FRAG_SPV,
+      &__GLSL_SHADER_FRAG_LINEAR_SPV,
+      &__GLSL_SHADER_FRAG_SCRGB_SPV,
+      &__GLSL_SHADER_FRAG_HDR10_SPV,
+      &__GLSL_SHADER_FRAG_BINDLESS_SPV,
+      &__GLSL_SHADER_FRAG_BINDLESS_LINEAR_SPV,
+      &__GLSL_SHADER_FRAG_BINDLESS_SCRGB_SPV,
+      &__GLSL_SHADER_FRAG_BINDLESS_HDR10_SPV,
+    ];
+    for spv in shaders {
+      // OpSpecConstantFalse by default, OpSpecConstantTrue for the premultiplied alpha pipeline.
+      assert_eq!(premultiply_color_opcodes(spv), vec![49]);
+      let straight = HalaImGui::specialize_premultiply_color(spv, false);
+      assert_eq!(straight, spv);
+      let premultiplied = HalaImGui::specialize_premultiply_color(spv, true);
+      assert_eq!(premultiply_color_opcodes(&premultiplied), vec![48]);
+      assert_eq!(premultiplied.len(), spv.len());
+    }
+  }
+
+  #[test]
+  fn frag_shader_without_premultiply_color_is_kept() {
+    // The vertex shader has no specialization constant.
+    let spv = HalaImGui::specialize_premultiply_color(&__GLSL_SHADER_VERT_SPV, true);
+    assert_eq!(spv, __GLSL_SHADER_VERT_SPV);
+  }
+
+  #[test]
+  fn index_type_follows_draw_index_size() {
+    assert_eq!(HalaImGui::index_type_of_size(2), hala_gfx::HalaIndexType::UINT16);
+    assert_eq!(HalaImGui::index_type_of_size(4), hala_gfx::HalaIndexType::UINT32);
+    assert_eq!(HalaImGui::index_type(), HalaImGui::index_type_of_size(std::mem::size_of::<imgui::DrawIdx>()));
+  }
+
+  #[test]
+  fn contiguous_commands_merge() {
+    let mut batch = new_batch(FONT_TEXTURE_ID, 0, 6, 0);
+    assert!(batch.merge(&new_batch(FONT_TEXTURE_ID, 6, 12, 0)));
+    assert_eq!(batch.first_index, 0);
+    assert_eq!(batch.index_count, 18);
+  }
+
+  #[test]
+  fn commands_with_other_vertex_offset_do_not_merge() {
+    // A draw list over 64K vertices continues with a new vertex offset and contiguous indices.
+    let mut batch = new_batch(FONT_TEXTURE_ID, 0, 6, 0);
+    assert!(!batch.merge(&new_batch(FONT_TEXTURE_ID, 6, 6, 65536)));
+    assert_eq!(batch.index_count, 6);
+  }
+
+  #[test]
+  fn commands_with_other_state_do_not_merge() {
+    let mut batch = new_batch(FONT_TEXTURE_ID, 0, 6, 0);
+    assert!(!batch.merge(&new_batch(FONT_TEXTURE_ID + 1, 6, 6, 0)));
+    assert!(!batch.merge(&new_batch(FONT_TEXTURE_ID, 12, 6, 0)));
+    let mut clipped = new_batch(FONT_TEXTURE_ID, 6, 6, 0);
+    clipped.clip_rect = [0.0, 0.0, 50.0, 50.0];
+    assert!(!batch.merge(&clipped));
+    assert_eq!(batch.index_count, 6);
+  }
+
+  #[test]
+  fn clip_rect_is_projected_and_clamped() {
+    let clip_rect = HalaImGui::project_clip_rect([10.0, 20.0, 30.0, 40.0], [5.0, 10.0], [2.0, 2.0], [1000.0, 1000.0]);
+    assert_eq!(clip_rect, Some([10.0, 20.0, 50.0, 60.0]));
+
+    let clip_rect = HalaImGui::project_clip_rect([-10.0, -10.0, 200.0, 200.0], [0.0, 0.0], [1.0, 1.0], [100.0, 50.0]);
+    assert_eq!(clip_rect, Some([0.0, 0.0, 100.0, 50.0]));
+  }
+
+  #[test]
+  fn empty_clip_rect_is_skipped() {
+    assert_eq!(HalaImGui::project_clip_rect([10.0, 10.0, 10.0, 20.0], [0.0, 0.0], [1.0, 1.0], [100.0, 100.0]), None);
+    assert_eq!(HalaImGui::project_clip_rect([150.0, 0.0, 200.0, 20.0], [0.0, 0.0], [1.0, 1.0], [100.0, 100.0]), None);
+  }
+
+}
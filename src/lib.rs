@@ -1,5 +1,22 @@
 mod application;
+mod buffer;
+mod clipboard;
+mod error;
+mod font;
+mod gamepad;
+mod headless;
+mod ime;
 mod imgui;
+mod stats;
+mod texture;
 
 pub use application::*;
-pub use imgui::*;
\ No newline at end of file
+pub use clipboard::*;
+pub use error::*;
+pub use font::*;
+pub use gamepad::*;
+pub use headless::*;
+pub use ime::*;
+pub use imgui::*;
+pub use stats::*;
+pub use texture::*;
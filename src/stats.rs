@@ -0,0 +1,170 @@
+use std::rc::Rc;
+
+/// The render statistics of the last frame drawn by the ImGUI context.
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub struct HalaImGuiStats {
+  /// The number of the draw lists.
+  pub draw_lists: u32,
+  /// The number of the vertices.
+  pub vertices: u32,
+  /// The number of the indices.
+  pub indices: u32,
+  /// The bytes of the vertex/index data uploaded.
+  pub uploaded_bytes: u64,
+  /// The number of the vertex/index buffer reallocations.
+  pub buffer_reallocations: u32,
+  /// The number of the draw commands before batching.
+  pub draw_commands: u32,
+  /// The number of the draw calls after batching.
+  pub draw_calls: u32,
+  /// The number of the descriptor set binds, or the texture index pushes in the bindless mode.
+  pub descriptor_binds: u32,
+  /// The number of the scissor changes.
+  pub scissor_sets: u32,
+  /// The GPU time of the ImGUI pass in milliseconds, None if the GPU timing is disabled or not available yet.
+  /// It is measured for the last frame using the same frame slot, so it lags behind by the frames in flight.
+  pub gpu_time_ms: Option<f32>,
+}
+
+/// The implementation of the render statistics.
+impl HalaImGuiStats {
+
+  /// Show the stats in an overlay window.
+  /// param ui: The UI of the current frame.
+  pub(crate) fn show_overlay(&self, ui: &imgui::Ui) {
+    ui.window("ImGUI Stats")
+      .position([10.0, 10.0], imgui::Condition::FirstUseEver)
+      .bg_alpha(0.6)
+      .always_auto_resize(true)
+      .no_decoration()
+      .no_nav()
+      .build(|| {
+        ui.text(format!("Draw lists: {}", self.draw_lists));
+        ui.text(format!("Vertices: {}, indices: {}", self.vertices, self.indices));
+        ui.text(format!("Uploaded: {:.1} KiB, reallocations: {}", self.uploaded_bytes as f64 / 1024.0, self.buffer_reallocations));
+        ui.text(format!("Draw calls: {} of {} commands", self.draw_calls, self.draw_commands));
+        ui.text(format!("Descriptor binds: {}, scissors: {}", self.descriptor_binds, self.scissor_sets));
+        match self.gpu_time_ms {
+          Some(gpu_time_ms) => ui.text(format!("GPU time: {:.3} ms", gpu_time_ms)),
+          None => ui.text("GPU time: N/A"),
+        }
+      });
+  }
+
+}
+
+/// The GPU timer of the ImGUI pass with a pair of timestamp queries per frame slot.
+pub(crate) struct HalaImGuiGpuTimer {
+  query_pool: hala_gfx::HalaQueryPool,
+  /// The nanoseconds per timestamp tick.
+  timestamp_period: f32,
+  /// The mask of the valid bits of the timestamps.
+  timestamp_mask: u64,
+  /// Whether the queries of the frame slots are reset in the command buffer being recorded.
+  reset: Vec<bool>,
+  /// Whether the queries of the frame slots are written by the last submission.
+  written: Vec<bool>,
+}
+
+/// The implementation of the GPU timer.
+impl HalaImGuiGpuTimer {
+
+  /// Create the GPU timer.
+  /// The graphics queue must support the timestamps, see is_supported.
+  /// param context: The Vulkan context.
+  /// param frames_in_flight: The number of frames in flight.
+  /// return: The GPU timer.
+  pub(crate) fn new(context: &hala_gfx::HalaContext, frames_in_flight: usize) -> Result<Self, hala_gfx::HalaGfxError> {
+    let query_pool = hala_gfx::HalaQueryPool::new(
+      Rc::clone(&context.logical_device),
+      hala_gfx::HalaQueryType::TIMESTAMP,
+      (frames_in_flight * 2) as u32,
+      "imgui_timestamp.querypool",
+    )?;
+    let timestamp_valid_bits = context.physical_device.timestamp_valid_bits;
+
+    Ok(Self {
+      query_pool,
+      timestamp_period: context.physical_device.timestamp_period,
+      timestamp_mask: if timestamp_valid_bits >= 64 { u64::MAX } else { (1u64 << timestamp_valid_bits) - 1 },
+      reset: vec![false; frames_in_flight],
+      written: vec![false; frames_in_flight],
+    })
+  }
+
+  /// Whether the graphics queue of the context writes the timestamps.
+  /// param context: The Vulkan context.
+  /// return: Whether the timestamps are supported.
+  pub(crate) fn is_supported(context: &hala_gfx::HalaContext) -> bool {
+    context.physical_device.timestamp_valid_bits > 0
+  }
+
+  /// Get the number of frames in flight of the timer.
+  /// return: The number of frames in flight.
+  pub(crate) fn frames_in_flight(&self) -> usize {
+    self.written.len()
+  }
+
+  /// Read the GPU time of the last submission of the frame slot.
+  /// The fence of the last submission using the frame slot must have been waited.
+  /// param frame_slot: The frame slot.
+  /// return: The GPU time in milliseconds, None if the queries are not written.
+  pub(crate) fn resolve(&mut self, frame_slot: usize) -> Option<f32> {
+    if !std::mem::take(&mut self.written[frame_slot]) {
+      return None;
+    }
+
+    let timestamps = self.query_pool.get_results_u64((frame_slot * 2) as u32, 2).ok()?;
+    let ticks = timestamps[1].wrapping_sub(timestamps[0]) & self.timestamp_mask;
+
+    Some(ticks as f32 * self.timestamp_period / 1_000_000.0)
+  }
+
+  /// Reset the queries of the frame slot in the command buffer.
+  /// It must be recorded outside of a render pass.
+  /// param index: The index of the command buffer.
+  /// param command_buffers: The command buffers.
+  /// param frame_slot: The frame slot.
+  pub(crate) fn reset(&mut self, index: usize, command_buffers: &hala_gfx::HalaCommandBufferSet, frame_slot: usize) {
+    command_buffers.reset_query_pool(index, &self.query_pool, (frame_slot * 2) as u32, 2);
+    self.reset[frame_slot] = true;
+  }
+
+  /// Write the timestamp at the beginning of the ImGUI pass.
+  /// Nothing is written if the queries of the frame slot are not reset.
+  /// param index: The index of the command buffer.
+  /// param command_buffers: The command buffers.
+  /// param frame_slot: The frame slot.
+  pub(crate) fn begin(&self, index: usize, command_buffers: &hala_gfx::HalaCommandBufferSet, frame_slot: usize) {
+    if !self.reset[frame_slot] {
+      return;
+    }
+
+    command_buffers.write_timestamp(
+      index,
+      hala_gfx::HalaPipelineStageFlags2::TOP_OF_PIPE,
+      &self.query_pool,
+      (frame_slot * 2) as u32,
+    );
+  }
+
+  /// Write the timestamp at the end of the ImGUI pass.
+  /// Nothing is written if the queries of the frame slot are not reset.
+  /// param index: The index of the command buffer.
+  /// param command_buffers: The command buffers.
+  /// param frame_slot: The frame slot.
+  pub(crate) fn end(&mut self, index: usize, command_buffers: &hala_gfx::HalaCommandBufferSet, frame_slot: usize) {
+    if !std::mem::take(&mut self.reset[frame_slot]) {
+      return;
+    }
+
+    command_buffers.write_timestamp(
+      index,
+      hala_gfx::HalaPipelineStageFlags2::BOTTOM_OF_PIPE,
+      &self.query_pool,
+      (frame_slot * 2 + 1) as u32,
+    );
+    self.written[frame_slot] = true;
+  }
+
+}
@@ -0,0 +1,100 @@
+use std::{
+  cell::RefCell,
+  collections::HashMap,
+  rc::Rc,
+};
+
+use anyhow::Result;
+
+use crate::imgui::HalaImGuiBlendMode;
+
+/// The filter of the texture sampler.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum HalaImGuiFilter {
+  /// Bilinear filtering.
+  #[default]
+  Linear,
+  /// Nearest filtering, for the pixel art.
+  Nearest,
+}
+
+/// The address mode of the texture sampler.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum HalaImGuiAddressMode {
+  #[default]
+  Repeat,
+  MirroredRepeat,
+  /// Clamp to the edge texels, for the atlases.
+  ClampToEdge,
+}
+
+/// The options of the texture sampler.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub struct HalaImGuiSamplerOptions {
+  pub filter: HalaImGuiFilter,
+  pub address_mode: HalaImGuiAddressMode,
+}
+
+/// The options of a texture registered in the ImGUI context.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct HalaImGuiTextureOptions {
+  /// The sampler options.
+  pub sampler: HalaImGuiSamplerOptions,
+  /// The alpha mode of the texels, None to use the blend mode of the ImGUI context.
+  pub alpha_mode: Option<HalaImGuiBlendMode>,
+}
+
+/// The samplers shared by the textures with the same options.
+pub(crate) struct HalaImGuiSamplers {
+  samplers: HashMap<HalaImGuiSamplerOptions, hala_gfx::HalaSampler>,
+}
+
+/// The implementation of the samplers.
+impl HalaImGuiSamplers {
+
+  /// Create an empty sampler cache.
+  /// return: The samplers.
+  pub(crate) fn new() -> Self {
+    Self {
+      samplers: HashMap::new(),
+    }
+  }
+
+  /// Get the sampler of the options, create it on the first use.
+  /// param logical_device: The logical device.
+  /// param options: The sampler options.
+  /// return: The sampler.
+  pub(crate) fn get_or_create(
+    &mut self,
+    logical_device: &Rc<RefCell<hala_gfx::HalaLogicalDevice>>,
+    options: &HalaImGuiSamplerOptions,
+  ) -> Result<&hala_gfx::HalaSampler> {
+    if !self.samplers.contains_key(options) {
+      let (filter, mipmap_mode) = match options.filter {
+        HalaImGuiFilter::Linear => (hala_gfx::HalaFilter::LINEAR, hala_gfx::HalaSamplerMipmapMode::LINEAR),
+        HalaImGuiFilter::Nearest => (hala_gfx::HalaFilter::NEAREST, hala_gfx::HalaSamplerMipmapMode::NEAREST),
+      };
+      let address_mode = match options.address_mode {
+        HalaImGuiAddressMode::Repeat => hala_gfx::HalaSamplerAddressMode::REPEAT,
+        HalaImGuiAddressMode::MirroredRepeat => hala_gfx::HalaSamplerAddressMode::MIRRORED_REPEAT,
+        HalaImGuiAddressMode::ClampToEdge => hala_gfx::HalaSamplerAddressMode::CLAMP_TO_EDGE,
+      };
+      let sampler = hala_gfx::HalaSampler::new(
+        Rc::clone(logical_device),
+        (filter, filter),
+        mipmap_mode,
+        (address_mode, address_mode, address_mode),
+        0.0,
+        false,
+        1.0,
+        (-1000.0, 1000.0),
+        &format!("imgui_{:?}_{:?}.sampler", options.filter, options.address_mode).to_lowercase(),
+      )?;
+      log::debug!("ImGUI sampler {:?} created.", options);
+      self.samplers.insert(*options, sampler);
+    }
+
+    Ok(self.samplers.get(options).unwrap())
+  }
+
+}